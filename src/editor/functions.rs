@@ -0,0 +1,298 @@
+//! User functions and script calls: collapsing a selection of nodes into a
+//! function, and keeping CallScript ports in line with the saved scripts.
+
+use super::node_ports::get_ports_for_type;
+use crate::graph::{BlueprintGraph, CallSignature, Connection, Function, Node};
+use crate::node_types::{DataType, NodeType};
+use anyhow::{Result, bail};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime};
+use uuid::Uuid;
+
+/// How often called scripts are checked for changes on disk
+const SCRIPT_REFRESH: Duration = Duration::from_secs(1);
+
+/// Distance between the moved nodes and the generated parameter nodes
+const PARAM_GAP: f32 = 250.0;
+/// Vertical spacing of generated parameter nodes
+const PARAM_SPACING: f32 = 80.0;
+
+/// Move the `selected` nodes of `graph` into a new function `name` and put
+/// a CallFunction node in their place.
+///
+/// Data connections crossing the selection become InputParam / OutputParam
+/// nodes, one per source output. An incoming exec connection starts the body
+/// at its Entry node; an outgoing one continues after the call. The caller
+/// adds the returned function to the graph's library and then runs
+/// `sync_function_calls` to give the call node its ports.
+pub fn collapse_to_function(
+    graph: &mut BlueprintGraph,
+    selected: &HashSet<Uuid>,
+    name: &str,
+) -> Result<Function> {
+    let moved: Vec<Node> = graph
+        .nodes
+        .values()
+        .filter(|n| selected.contains(&n.id))
+        .cloned()
+        .collect();
+    if moved.is_empty() {
+        bail!("Select the nodes to move into the function first");
+    }
+    if moved.iter().any(|n| {
+        n.node_type.is_event()
+            || matches!(
+                n.node_type,
+                NodeType::Entry | NodeType::InputParam { .. } | NodeType::OutputParam { .. }
+            )
+    }) {
+        bail!("Event, Entry and parameter nodes cannot be moved into a function");
+    }
+    let is_exec = |node_id: Uuid, port: &str, input: bool| {
+        graph.nodes.get(&node_id).is_some_and(|n| {
+            let ports = if input { &n.inputs } else { &n.outputs };
+            ports
+                .iter()
+                .any(|p| p.name == port && p.data_type == DataType::ExecutionFlow)
+        })
+    };
+    let node_y = |id: Uuid| graph.nodes.get(&id).map_or(0.0, |n| n.position.1);
+
+    let mut internal = Vec::new();
+    let mut incoming = Vec::new();
+    let mut outgoing = Vec::new();
+    let mut untouched = Vec::new();
+    for conn in &graph.connections {
+        match (selected.contains(&conn.from_node), selected.contains(&conn.to_node)) {
+            (true, true) => internal.push(conn.clone()),
+            (false, true) => incoming.push(conn.clone()),
+            (true, false) => outgoing.push(conn.clone()),
+            (false, false) => untouched.push(conn.clone()),
+        }
+    }
+    // Top to bottom, so parameters come out in the order they are used
+    incoming.sort_by(|a, b| node_y(a.to_node).total_cmp(&node_y(b.to_node)));
+    outgoing.sort_by(|a, b| node_y(a.from_node).total_cmp(&node_y(b.from_node)));
+
+    let (exec_in, data_in): (Vec<_>, Vec<_>) = incoming
+        .into_iter()
+        .partition(|c| is_exec(c.to_node, &c.to_port, true));
+    let (exec_out, data_out): (Vec<_>, Vec<_>) = outgoing
+        .into_iter()
+        .partition(|c| is_exec(c.from_node, &c.from_port, false));
+
+    let start = distinct(exec_in.iter().map(|c| (c.to_node, c.to_port.clone())));
+    let after = distinct(exec_out.iter().map(|c| (c.to_node, c.to_port.clone())));
+    if start.len() > 1 {
+        bail!("The selection can only be entered at one node");
+    }
+    if after.len() > 1 {
+        bail!("The selection can only continue to one node outside it");
+    }
+
+    let min_x = moved.iter().map(|n| n.position.0).fold(f32::INFINITY, f32::min);
+    let min_y = moved.iter().map(|n| n.position.1).fold(f32::INFINITY, f32::min);
+    let max_x = moved.iter().map(|n| n.position.0).fold(f32::NEG_INFINITY, f32::max);
+
+    let mut body = BlueprintGraph::default();
+    let call = new_node(
+        NodeType::CallFunction {
+            name: name.to_string(),
+        },
+        (min_x, min_y),
+    );
+    let mut outside = untouched;
+
+    // Exec: Entry starts where the selection was entered
+    let entry = new_node(NodeType::Entry, (min_x - PARAM_GAP, min_y));
+    if let Some((to_node, to_port)) = start.first() {
+        body.connections.push(connect(entry.id, "Next", *to_node, to_port));
+    }
+    for conn in &exec_in {
+        outside.push(connect(conn.from_node, &conn.from_port, call.id, "In"));
+    }
+    for (to_node, to_port) in &after {
+        outside.push(connect(call.id, "Next", *to_node, to_port));
+    }
+    body.nodes.insert(entry.id, entry);
+
+    // Data in: one InputParam per outside output read by the selection
+    let mut params: Vec<((Uuid, String), Uuid, String)> = Vec::new();
+    for conn in &data_in {
+        let source = (conn.from_node, conn.from_port.clone());
+        let param_id = match params.iter().find(|(s, ..)| *s == source) {
+            Some((_, id, _)) => *id,
+            None => {
+                let used: Vec<&str> = params.iter().map(|(_, _, n)| n.as_str()).collect();
+                let param_name = unique_name(&conn.from_port, &used);
+                let y = min_y + (params.len() + 1) as f32 * PARAM_SPACING;
+                let node = new_node(
+                    NodeType::InputParam {
+                        name: param_name.clone(),
+                    },
+                    (min_x - PARAM_GAP, y),
+                );
+                let id = node.id;
+                body.nodes.insert(id, node);
+                outside.push(connect(conn.from_node, &conn.from_port, call.id, &param_name));
+                params.push((source, id, param_name));
+                id
+            }
+        };
+        body.connections.push(connect(param_id, "Value", conn.to_node, &conn.to_port));
+    }
+
+    // Data out: one OutputParam per selection output read outside
+    let mut results: Vec<((Uuid, String), String)> = Vec::new();
+    for conn in &data_out {
+        let source = (conn.from_node, conn.from_port.clone());
+        let result_name = match results.iter().find(|(s, _)| *s == source) {
+            Some((_, name)) => name.clone(),
+            None => {
+                let used: Vec<&str> = results.iter().map(|(_, n)| n.as_str()).collect();
+                let result_name = unique_name(&conn.from_port, &used);
+                let y = min_y + results.len() as f32 * PARAM_SPACING;
+                let node = new_node(
+                    NodeType::OutputParam {
+                        name: result_name.clone(),
+                    },
+                    (max_x + PARAM_GAP, y),
+                );
+                body.connections
+                    .push(connect(conn.from_node, &conn.from_port, node.id, "Value"));
+                body.nodes.insert(node.id, node);
+                results.push((source, result_name.clone()));
+                result_name
+            }
+        };
+        outside.push(connect(call.id, &result_name, conn.to_node, &conn.to_port));
+    }
+
+    body.connections.extend(internal);
+    for mut node in moved {
+        graph.nodes.remove(&node.id);
+        node.group_id = None;
+        body.nodes.insert(node.id, node);
+    }
+    for group in graph.groups.values_mut() {
+        group.contained_nodes.retain(|id| !selected.contains(id));
+    }
+    graph.nodes.insert(call.id, call);
+    graph.connections = outside;
+
+    Ok(Function {
+        name: name.to_string(),
+        body,
+    })
+}
+
+fn new_node(node_type: NodeType, position: (f32, f32)) -> Node {
+    let (inputs, outputs) = get_ports_for_type(&node_type);
+    Node {
+        id: Uuid::new_v4(),
+        node_type,
+        position,
+        inputs,
+        outputs,
+        z_order: 0,
+        display_name: None,
+        enabled: true,
+        group_id: None,
+        note_text: String::new(),
+        note_size: (200.0, 100.0),
+    }
+}
+
+fn connect(from_node: Uuid, from_port: &str, to_node: Uuid, to_port: &str) -> Connection {
+    Connection {
+        from_node,
+        from_port: from_port.to_string(),
+        to_node,
+        to_port: to_port.to_string(),
+    }
+}
+
+fn distinct<T: PartialEq>(items: impl Iterator<Item = T>) -> Vec<T> {
+    let mut out = Vec::new();
+    for item in items {
+        if !out.contains(&item) {
+            out.push(item);
+        }
+    }
+    out
+}
+
+/// `base`, or `base 2`, `base 3`... if taken. Avoids the call node's own
+/// port names.
+fn unique_name(base: &str, used: &[&str]) -> String {
+    let reserved = ["In", "Next", "Error", "ErrorMessage"];
+    let taken = |name: &str| used.contains(&name) || reserved.contains(&name);
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|i| format!("{} {}", base, i))
+        .find(|name| !taken(name))
+        .unwrap()
+}
+
+/// A new, empty function: its body holds only the Entry node
+pub fn new_function(name: &str) -> Function {
+    let entry = new_node(NodeType::Entry, (100.0, 100.0));
+    let mut body = BlueprintGraph::default();
+    body.nodes.insert(entry.id, entry);
+    Function {
+        name: name.to_string(),
+        body,
+    }
+}
+
+/// First free `"{base} {n}"` function name of `graph`
+pub fn free_function_name(graph: &BlueprintGraph, base: &str) -> String {
+    (1..)
+        .map(|i| format!("{} {}", base, i))
+        .find(|name| graph.function(name).is_none())
+        .unwrap()
+}
+
+/// Signatures of the scripts run by CallScript nodes, re-read when a script
+/// file changes.
+#[derive(Default)]
+pub struct ScriptSignatures {
+    signatures: HashMap<String, CallSignature>,
+    modified: HashMap<String, SystemTime>,
+    checked: Option<Instant>,
+}
+
+impl ScriptSignatures {
+    /// Give the CallScript nodes of `graph` the ports of the scripts they
+    /// run. Checks the disk at most once per [`SCRIPT_REFRESH`]; returns
+    /// whether any node changed.
+    pub fn sync(&mut self, graph: &mut BlueprintGraph) -> bool {
+        if self.checked.is_some_and(|t| t.elapsed() < SCRIPT_REFRESH) {
+            return false;
+        }
+        self.checked = Some(Instant::now());
+
+        let names = graph.called_scripts();
+        self.signatures.retain(|name, _| names.contains(name));
+        self.modified.retain(|name, _| names.contains(name));
+        for name in names {
+            let path = BlueprintGraph::script_path(&name);
+            let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+                // Missing script: leave the node's ports as they are
+                self.signatures.remove(&name);
+                self.modified.remove(&name);
+                continue;
+            };
+            if self.modified.get(&name) == Some(&modified) {
+                continue;
+            }
+            if let Ok(script) = BlueprintGraph::load(&path) {
+                self.signatures.insert(name.clone(), script.script_signature());
+                self.modified.insert(name, modified);
+            }
+        }
+        graph.sync_script_calls(&self.signatures)
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides the visual node-based graph editor.
 //!
 //! ## Submodules
+//! - [`functions`]: Collapsing a selection into a user function, CallScript ports
 //! - [`node_ports`]: Port definitions for all node types
 //! - [`style`]: Editor styling and clipboard data
 //! - [`utils`]: Geometry, color, and rendering utilities
@@ -11,6 +12,7 @@
 //! [`GraphEditor`] - The main graph editor widget
 
 // Submodules
+pub mod functions;
 pub mod node_ports;
 pub mod style;
 pub mod utils;
@@ -51,6 +53,15 @@ pub struct GraphEditor {
     pub image_thumbnail_cache: std::collections::HashMap<String, egui::TextureHandle>,
     /// List of available template images (cached on first access)
     pub available_templates: Option<Vec<String>>,
+    /// Nodes the interpreter pauses before (toggle with F9 or the context menu)
+    pub breakpoints: std::collections::HashSet<Uuid>,
+    /// Set when breakpoints were toggled this frame, so a running graph can be updated
+    pub breakpoints_changed: bool,
+    /// Node the interpreter is currently paused before
+    pub paused_node: Option<Uuid>,
+    /// "Collapse to Function" was chosen; the host moves the selected nodes
+    /// into a new function and clears the flag
+    pub collapse_requested: bool,
 }
 
 impl Default for GraphEditor {
@@ -76,6 +87,10 @@ impl Default for GraphEditor {
             node_execution_times: std::collections::HashMap::new(),
             image_thumbnail_cache: std::collections::HashMap::new(),
             available_templates: None,
+            breakpoints: std::collections::HashSet::new(),
+            breakpoints_changed: false,
+            paused_node: None,
+            collapse_requested: false,
         }
     }
 }
@@ -101,6 +116,7 @@ impl GraphEditor {
         let mut input_any_released = false;
         let mut input_modifiers = egui::Modifiers::default();
         let mut input_space = false;
+        let mut input_toggle_breakpoint = false;
 
         ui.input(|i| {
             // Pan with Middle Mouse or Alt + Left Mouse
@@ -152,6 +168,7 @@ impl GraphEditor {
             input_any_released = i.pointer.any_released();
             input_modifiers = i.modifiers;
             input_space = i.key_pressed(egui::Key::Space);
+            input_toggle_breakpoint = i.key_pressed(egui::Key::F9);
 
             // Zoom Shortcuts
             if i.modifiers.command {
@@ -186,6 +203,16 @@ impl GraphEditor {
             }
         });
 
+        // Toggle breakpoints on selected nodes (F9)
+        if input_toggle_breakpoint && !ui.memory(|m| m.focused().is_some()) {
+            for id in &self.selected_nodes {
+                if !self.breakpoints.remove(id) {
+                    self.breakpoints.insert(*id);
+                }
+            }
+            self.breakpoints_changed = true;
+        }
+
         let canvas_offset = ui.max_rect().min;
 
         // Pre-calculate node sizes (needed for both drawing and connection lines)
@@ -299,6 +326,10 @@ impl GraphEditor {
                             ui.close();
                         }
                     }
+                    if ui.button("ƒ Collapse to Function").clicked() {
+                        self.collapse_requested = true;
+                        ui.close();
+                    }
                 });
         }
 
@@ -836,6 +867,11 @@ impl GraphEditor {
                                         name: "Event Tick".into(),
                                     },
                                 ),
+                                ("On Start", crate::node_types::NodeType::OnStart),
+                                ("On Interval", crate::node_types::NodeType::OnInterval),
+                                ("On Variable Changed", crate::node_types::NodeType::OnVariableChanged),
+                                ("On Hotkey", crate::node_types::NodeType::OnHotkey),
+                                ("On Stop", crate::node_types::NodeType::OnStop),
                                 (
                                     "Print String",
                                     crate::node_types::NodeType::BlueprintFunction {
@@ -846,13 +882,27 @@ impl GraphEditor {
                                 ("Branch", crate::node_types::NodeType::Branch),
                                 ("For Loop", crate::node_types::NodeType::ForLoop),
                                 ("For Loop Async", crate::node_types::NodeType::ForLoopAsync),
+                                ("For Each", crate::node_types::NodeType::ForEach),
                                 ("For Each Line", crate::node_types::NodeType::ForEachLine),
                                 ("While Loop", crate::node_types::NodeType::WhileLoop),
                                 ("Delay", crate::node_types::NodeType::Delay),
                                 ("Get Timestamp", crate::node_types::NodeType::GetTimestamp),
                                 ("Sequence", crate::node_types::NodeType::Sequence),
+                                ("Parallel", crate::node_types::NodeType::Parallel),
+                                ("Join", crate::node_types::NodeType::Join),
                                 ("Gate", crate::node_types::NodeType::Gate),
                                 ("Wait For Condition", crate::node_types::NodeType::WaitForCondition),
+                                ("Try Catch", crate::node_types::NodeType::TryCatch),
+                                ("Break", crate::node_types::NodeType::Break),
+                                ("Continue", crate::node_types::NodeType::Continue),
+                                ("Return", crate::node_types::NodeType::Return),
+                                ("Switch On String", crate::node_types::NodeType::SwitchOnString),
+                                ("Switch On Int", crate::node_types::NodeType::SwitchOnInt),
+                                ("Do Once", crate::node_types::NodeType::DoOnce),
+                                ("Do N", crate::node_types::NodeType::DoN),
+                                ("Flip Flop", crate::node_types::NodeType::FlipFlop),
+                                ("Retry", crate::node_types::NodeType::Retry),
+                                ("With Timeout", crate::node_types::NodeType::WithTimeout),
                                 // Math
                                 ("Add", crate::node_types::NodeType::Add),
                                 ("Subtract", crate::node_types::NodeType::Subtract),
@@ -866,6 +916,16 @@ impl GraphEditor {
                                 ("Clamp", crate::node_types::NodeType::Clamp),
                                 ("Random", crate::node_types::NodeType::Random),
                                 ("Constant", crate::node_types::NodeType::Constant),
+                                // Geometry
+                                ("Make Point", crate::node_types::NodeType::MakePoint),
+                                ("Split Point", crate::node_types::NodeType::SplitPoint),
+                                ("Make Rect", crate::node_types::NodeType::MakeRect),
+                                ("Split Rect", crate::node_types::NodeType::SplitRect),
+                                ("Rect Center", crate::node_types::NodeType::RectCenter),
+                                ("Rect Offset", crate::node_types::NodeType::RectOffset),
+                                ("Point In Rect", crate::node_types::NodeType::PointInRect),
+                                ("Rect Union", crate::node_types::NodeType::RectUnion),
+                                ("Distance", crate::node_types::NodeType::Distance),
                                 // Comparison
                                 ("Equals (==)", crate::node_types::NodeType::Equals),
                                 ("Not Equals (!=)", crate::node_types::NodeType::NotEquals),
@@ -894,6 +954,10 @@ impl GraphEditor {
                                 ("String Join", crate::node_types::NodeType::StringJoin),
                                 ("String Between", crate::node_types::NodeType::StringBetween),
                                 ("String Trim", crate::node_types::NodeType::StringTrim),
+                                ("Regex Match", crate::node_types::NodeType::RegexMatch),
+                                ("Regex Capture", crate::node_types::NodeType::RegexCapture),
+                                ("Regex Find All", crate::node_types::NodeType::RegexFindAll),
+                                ("Regex Replace", crate::node_types::NodeType::RegexReplace),
                                 // Conversions
                                 ("To Integer", crate::node_types::NodeType::ToInteger),
                                 ("To Float", crate::node_types::NodeType::ToFloat),
@@ -915,6 +979,32 @@ impl GraphEditor {
                                         name: "MyVar".into(),
                                     },
                                 ),
+                                // Functions
+                                (
+                                    "Call Function",
+                                    crate::node_types::NodeType::CallFunction {
+                                        name: "MyFunction".into(),
+                                    },
+                                ),
+                                (
+                                    "Call Script",
+                                    crate::node_types::NodeType::CallScript {
+                                        name: "MyScript".into(),
+                                    },
+                                ),
+                                ("Function Entry", crate::node_types::NodeType::Entry),
+                                (
+                                    "Input Param",
+                                    crate::node_types::NodeType::InputParam {
+                                        name: "Param".into(),
+                                    },
+                                ),
+                                (
+                                    "Output Param",
+                                    crate::node_types::NodeType::OutputParam {
+                                        name: "Result".into(),
+                                    },
+                                ),
                                 // Utility
                                 // ("Notes", crate::node_types::NodeType::Notes), // Removed due to bugs
                                 // System Control
@@ -951,6 +1041,22 @@ impl GraphEditor {
                                 ("Array Get", crate::node_types::NodeType::ArrayGet),
                                 ("Array Set", crate::node_types::NodeType::ArraySet),
                                 ("Array Length", crate::node_types::NodeType::ArrayLength),
+                                ("Array Sort", crate::node_types::NodeType::ArraySort),
+                                ("Array Slice", crate::node_types::NodeType::ArraySlice),
+                                ("Array Join", crate::node_types::NodeType::ArrayJoin),
+                                ("Array Index Of", crate::node_types::NodeType::ArrayIndexOf),
+                                ("Array Contains", crate::node_types::NodeType::ArrayContains),
+                                ("Array Reverse", crate::node_types::NodeType::ArrayReverse),
+                                ("Array Unique", crate::node_types::NodeType::ArrayUnique),
+                                ("Array Remove At", crate::node_types::NodeType::ArrayRemoveAt),
+                                ("Array Filter", crate::node_types::NodeType::ArrayFilter),
+                                ("Array Map", crate::node_types::NodeType::ArrayMap),
+                                ("Map Create", crate::node_types::NodeType::MapCreate),
+                                ("Map Get", crate::node_types::NodeType::MapGet),
+                                ("Map Set", crate::node_types::NodeType::MapSet),
+                                ("Map Remove", crate::node_types::NodeType::MapRemove),
+                                ("Map Keys", crate::node_types::NodeType::MapKeys),
+                                ("Map Has Key", crate::node_types::NodeType::MapHasKey),
                                 ("JSON Parse", crate::node_types::NodeType::JSONParse),
                                 ("JSON Stringify", crate::node_types::NodeType::JSONStringify),
                                 ("HTTP Request", crate::node_types::NodeType::HTTPRequest),
@@ -1437,11 +1543,7 @@ impl GraphEditor {
         let mut y_offset = 30.0 * self.zoom;
 
         // Fix set variable layout overlap
-        if matches!(
-            node.node_type,
-            crate::node_types::NodeType::GetVariable { .. }
-                | crate::node_types::NodeType::SetVariable { .. }
-        ) {
+        if node.node_type.has_name_field() {
             y_offset += 20.0 * self.zoom;
         }
 
@@ -1476,11 +1578,7 @@ impl GraphEditor {
 
         let mut y_offset = 30.0 * self.zoom;
 
-        if matches!(
-            node.node_type,
-            crate::node_types::NodeType::GetVariable { .. }
-                | crate::node_types::NodeType::SetVariable { .. }
-        ) {
+        if node.node_type.has_name_field() {
             y_offset += 20.0 * self.zoom;
         }
 
@@ -1584,6 +1682,16 @@ impl GraphEditor {
             egui::StrokeKind::Middle,
         );
 
+        // Paused-before highlight
+        if self.paused_node == Some(node.id) {
+            ui.painter().rect_stroke(
+                node_rect.expand(4.0),
+                5.0,
+                Stroke::new(3.0, Color32::from_rgb(255, 165, 0)),
+                egui::StrokeKind::Middle,
+            );
+        }
+
         let category = match &node.node_type {
             crate::node_types::NodeType::BlueprintFunction { name } => {
                 if name.starts_with("Event") {
@@ -1592,6 +1700,12 @@ impl GraphEditor {
                     "Function"
                 }
             }
+            // Events
+            crate::node_types::NodeType::OnStart
+            | crate::node_types::NodeType::OnInterval
+            | crate::node_types::NodeType::OnVariableChanged
+            | crate::node_types::NodeType::OnHotkey
+            | crate::node_types::NodeType::OnStop => "Event",
             // Math Operations
             crate::node_types::NodeType::Add
             | crate::node_types::NodeType::Subtract
@@ -1604,7 +1718,16 @@ impl GraphEditor {
             | crate::node_types::NodeType::Max
             | crate::node_types::NodeType::Clamp
             | crate::node_types::NodeType::Random
-            | crate::node_types::NodeType::Constant => "Math",
+            | crate::node_types::NodeType::Constant
+            | crate::node_types::NodeType::MakePoint
+            | crate::node_types::NodeType::SplitPoint
+            | crate::node_types::NodeType::MakeRect
+            | crate::node_types::NodeType::SplitRect
+            | crate::node_types::NodeType::RectCenter
+            | crate::node_types::NodeType::RectOffset
+            | crate::node_types::NodeType::PointInRect
+            | crate::node_types::NodeType::RectUnion
+            | crate::node_types::NodeType::Distance => "Math",
 
             // Variables
             crate::node_types::NodeType::GetVariable { .. }
@@ -1620,7 +1743,11 @@ impl GraphEditor {
             | crate::node_types::NodeType::Replace
             | crate::node_types::NodeType::Format
             | crate::node_types::NodeType::ExtractAfter
-            | crate::node_types::NodeType::ExtractUntil => "String",
+            | crate::node_types::NodeType::ExtractUntil
+            | crate::node_types::NodeType::RegexMatch
+            | crate::node_types::NodeType::RegexCapture
+            | crate::node_types::NodeType::RegexFindAll
+            | crate::node_types::NodeType::RegexReplace => "String",
 
             // Type Conversions
             crate::node_types::NodeType::ToString
@@ -1644,8 +1771,21 @@ impl GraphEditor {
             // Control Flow
             crate::node_types::NodeType::Branch
             | crate::node_types::NodeType::ForLoop
+            | crate::node_types::NodeType::ForEach
             | crate::node_types::NodeType::WhileLoop
             | crate::node_types::NodeType::Sequence
+            | crate::node_types::NodeType::Parallel
+            | crate::node_types::NodeType::Join
+            | crate::node_types::NodeType::Break
+            | crate::node_types::NodeType::Continue
+            | crate::node_types::NodeType::Return
+            | crate::node_types::NodeType::SwitchOnString
+            | crate::node_types::NodeType::SwitchOnInt
+            | crate::node_types::NodeType::DoOnce
+            | crate::node_types::NodeType::DoN
+            | crate::node_types::NodeType::FlipFlop
+            | crate::node_types::NodeType::Retry
+            | crate::node_types::NodeType::WithTimeout
             | crate::node_types::NodeType::Gate
             | crate::node_types::NodeType::Entry => "ControlFlow",
 
@@ -1687,6 +1827,22 @@ impl GraphEditor {
             | crate::node_types::NodeType::ArrayGet
             | crate::node_types::NodeType::ArraySet
             | crate::node_types::NodeType::ArrayLength
+            | crate::node_types::NodeType::ArraySort
+            | crate::node_types::NodeType::ArraySlice
+            | crate::node_types::NodeType::ArrayJoin
+            | crate::node_types::NodeType::ArrayIndexOf
+            | crate::node_types::NodeType::ArrayContains
+            | crate::node_types::NodeType::ArrayReverse
+            | crate::node_types::NodeType::ArrayUnique
+            | crate::node_types::NodeType::ArrayRemoveAt
+            | crate::node_types::NodeType::ArrayFilter
+            | crate::node_types::NodeType::ArrayMap
+            | crate::node_types::NodeType::MapCreate
+            | crate::node_types::NodeType::MapGet
+            | crate::node_types::NodeType::MapSet
+            | crate::node_types::NodeType::MapRemove
+            | crate::node_types::NodeType::MapKeys
+            | crate::node_types::NodeType::MapHasKey
             | crate::node_types::NodeType::JSONParse
             | crate::node_types::NodeType::JSONStringify
             | crate::node_types::NodeType::HTTPRequest => "Data",
@@ -1705,8 +1861,10 @@ impl GraphEditor {
             | crate::node_types::NodeType::ImageSimilarity => "Recognition",
 
             // Input/Output Parameters
-            crate::node_types::NodeType::InputParam
-            | crate::node_types::NodeType::OutputParam => "Function",
+            crate::node_types::NodeType::InputParam { .. }
+            | crate::node_types::NodeType::OutputParam { .. }
+            | crate::node_types::NodeType::CallFunction { .. }
+            | crate::node_types::NodeType::CallScript { .. } => "Function",
 
             _ => "Default",
         };
@@ -1733,8 +1891,8 @@ impl GraphEditor {
 
         ui.painter().rect_filled(header_rect, 5.0, *header_color);
         
-        // Show enable/disable checkbox for Event Tick nodes
-        if matches!(&node.node_type, crate::node_types::NodeType::BlueprintFunction { name } if name == "Event Tick") {
+        // Show enable/disable checkbox for event nodes
+        if node.node_type.is_event() {
             let checkbox_size = 14.0 * self.zoom;
             let checkbox_pos = header_rect.left_top() + Vec2::new(4.0 * self.zoom, (header_rect.height() - checkbox_size) / 2.0);
             let checkbox_rect = Rect::from_min_size(checkbox_pos, Vec2::splat(checkbox_size));
@@ -1767,6 +1925,13 @@ impl GraphEditor {
             checkbox_response.on_hover_text(if node.enabled { "Enabled - Click to disable" } else { "Disabled - Click to enable" });
         }
 
+        // Breakpoint marker
+        if self.breakpoints.contains(&node.id) {
+            let radius = 5.0 * self.zoom;
+            let center = header_rect.right_top() + Vec2::new(-radius - 4.0 * self.zoom, header_rect.height() / 2.0);
+            ui.painter().circle(center, radius, Color32::from_rgb(220, 40, 40), Stroke::new(1.0, Color32::WHITE));
+        }
+
         // Show text edit if this node is being edited, otherwise show title
         // Cancel editing if node is no longer selected
         if self.editing_node_name == Some(node.id) && !self.selected_nodes.contains(&node.id) {
@@ -1812,9 +1977,13 @@ impl GraphEditor {
             );
         }
 
-        // Custom UI for Variable Node Name
+        // Custom UI for Variable / Parameter / Function Name
         if let crate::node_types::NodeType::GetVariable { name }
-        | crate::node_types::NodeType::SetVariable { name } = &mut node.node_type
+        | crate::node_types::NodeType::SetVariable { name }
+        | crate::node_types::NodeType::InputParam { name }
+        | crate::node_types::NodeType::OutputParam { name }
+        | crate::node_types::NodeType::CallFunction { name }
+        | crate::node_types::NodeType::CallScript { name } = &mut node.node_type
         {
             let edit_rect = Rect::from_min_size(
                 screen_pos + Vec2::new(10.0 * self.zoom, 25.0 * self.zoom),
@@ -1832,11 +2001,7 @@ impl GraphEditor {
         // Draw Ports & Inline Editors
         let mut y_offset = 30.0 * self.zoom;
 
-        if matches!(
-            node.node_type,
-            crate::node_types::NodeType::GetVariable { .. }
-                | crate::node_types::NodeType::SetVariable { .. }
-        ) {
+        if node.node_type.has_name_field() {
             y_offset += 20.0 * self.zoom;
         }
 
@@ -1964,6 +2129,7 @@ impl GraphEditor {
                                             | crate::node_types::NodeType::KeyDown
                                             | crate::node_types::NodeType::KeyUp
                                             | crate::node_types::NodeType::HotKey
+                                            | crate::node_types::NodeType::OnHotkey
                                     );
                                 let is_btn = input.name == "Button"
                                     && matches!(
@@ -2316,6 +2482,37 @@ impl GraphEditor {
             y_offset += 25.0 * self.zoom;
         }
 
+        // Switch nodes get a new case (input and exec output) once the last
+        // case has a value
+        if matches!(
+            node.node_type,
+            crate::node_types::NodeType::SwitchOnString
+                | crate::node_types::NodeType::SwitchOnInt
+        ) {
+            let cases = node.inputs.iter().filter(|p| p.name.starts_with("Case ")).count();
+            let last_filled = node
+                .inputs
+                .iter()
+                .rfind(|p| p.name.starts_with("Case "))
+                .is_some_and(|p| {
+                    matches!(&p.default_value, crate::graph::VariableValue::String(s) if !s.is_empty())
+                });
+            if last_filled {
+                let name = format!("Case {}", cases);
+                node.inputs.push(super::graph::Port {
+                    name: name.clone(),
+                    data_type: super::node_types::DataType::String,
+                    default_value: super::graph::VariableValue::String("".into()),
+                });
+                node.outputs.push(super::graph::Port {
+                    name,
+                    data_type: super::node_types::DataType::ExecutionFlow,
+                    default_value: super::graph::VariableValue::None,
+                });
+                content_changed = true;
+            }
+        }
+
         // StringJoin dynamic port expansion based on text content
         // When the last input has content, add a new empty input
         if matches!(node.node_type, crate::node_types::NodeType::StringJoin) {
@@ -2337,11 +2534,7 @@ impl GraphEditor {
         }
 
         let mut y_offset = 30.0 * self.zoom;
-        if matches!(
-            node.node_type,
-            crate::node_types::NodeType::GetVariable { .. }
-                | crate::node_types::NodeType::SetVariable { .. }
-        ) {
+        if node.node_type.has_name_field() {
             y_offset += 20.0 * self.zoom;
         }
 
@@ -2493,6 +2686,18 @@ impl GraphEditor {
                 }
                 ui.close();
             }
+            let breakpoint_label = if self.breakpoints.contains(&node.id) {
+                "Remove Breakpoint"
+            } else {
+                "Add Breakpoint"
+            };
+            if ui.button(breakpoint_label).on_hover_text("F9").clicked() {
+                if !self.breakpoints.remove(&node.id) {
+                    self.breakpoints.insert(node.id);
+                }
+                self.breakpoints_changed = true;
+                ui.close();
+            }
             if ui.button("Copy").clicked() {
                 copy_node = true;
                 ui.close();
@@ -2657,6 +2862,11 @@ impl GraphEditor {
                         "Function"
                     }
                 }
+                crate::node_types::NodeType::OnStart
+                | crate::node_types::NodeType::OnInterval
+                | crate::node_types::NodeType::OnVariableChanged
+                | crate::node_types::NodeType::OnHotkey
+                | crate::node_types::NodeType::OnStop => "Event",
                 crate::node_types::NodeType::Add
                 | crate::node_types::NodeType::Subtract
                 | crate::node_types::NodeType::Multiply
@@ -2705,11 +2915,7 @@ impl GraphEditor {
 
             // Match the draw_node calculation: 30.0 base + 20.0 for GetVariable/SetVariable offset
             let mut base_y = 30.0;
-            if matches!(
-                node.node_type,
-                crate::node_types::NodeType::GetVariable { .. }
-                    | crate::node_types::NodeType::SetVariable { .. }
-            ) {
+            if node.node_type.has_name_field() {
                 base_y += 20.0;
             }
 
@@ -11,6 +11,9 @@
 //! - **String**: Text values (khaki)
 //! - **Array**: Collection of values (orange)
 //! - **Custom**: Special types like "Any" (gray)
+//!
+//! Nodes for which [`NodeType::has_error_flow`] is true also get an `Error`
+//! exec output and an `ErrorMessage` string output, appended after their own ports.
 
 use crate::graph::{Port, VariableValue};
 use crate::node_types::{DataType, NodeType};
@@ -23,7 +26,7 @@ use crate::node_types::{DataType, NodeType};
 /// # Returns
 /// A tuple of (inputs, outputs) where each is a Vec<Port>
 pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
-    match node_type {
+    let (inputs, mut outputs) = match node_type {
             NodeType::BlueprintFunction { name } if name == "Event Tick" => (
                 vec![],
                 vec![Port {
@@ -515,6 +518,224 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                     default_value: VariableValue::Float(0.0),
                 }],
             ),
+            // MakePoint - Point from X and Y
+            NodeType::MakePoint => (
+                vec![
+                    Port {
+                        name: "X".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Y".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Point".into(),
+                        data_type: DataType::Point,
+                        default_value: VariableValue::Point(0, 0),
+                    },
+                ],
+            ),
+            // SplitPoint - X and Y of a point
+            NodeType::SplitPoint => (
+                vec![
+                    Port {
+                        name: "Point".into(),
+                        data_type: DataType::Point,
+                        default_value: VariableValue::Point(0, 0),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "X".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Y".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                ],
+            ),
+            // MakeRect - Rect from its top-left corner and size
+            NodeType::MakeRect => (
+                vec![
+                    Port {
+                        name: "X".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Y".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Width".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(100),
+                    },
+                    Port {
+                        name: "Height".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(100),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Rect".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::Rect(0, 0, 0, 0),
+                    },
+                ],
+            ),
+            // SplitRect - Corner and size of a rect
+            NodeType::SplitRect => (
+                vec![
+                    Port {
+                        name: "Rect".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::Rect(0, 0, 0, 0),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "X".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Y".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Width".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Height".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                ],
+            ),
+            // RectCenter - Center point of a rect
+            NodeType::RectCenter => (
+                vec![
+                    Port {
+                        name: "Rect".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::Rect(0, 0, 0, 0),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Center".into(),
+                        data_type: DataType::Point,
+                        default_value: VariableValue::Point(0, 0),
+                    },
+                ],
+            ),
+            // RectOffset - Rect moved by DX, DY
+            NodeType::RectOffset => (
+                vec![
+                    Port {
+                        name: "Rect".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::Rect(0, 0, 0, 0),
+                    },
+                    Port {
+                        name: "DX".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "DY".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Rect".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::Rect(0, 0, 0, 0),
+                    },
+                ],
+            ),
+            // PointInRect - Whether a point lies inside a rect
+            NodeType::PointInRect => (
+                vec![
+                    Port {
+                        name: "Point".into(),
+                        data_type: DataType::Point,
+                        default_value: VariableValue::Point(0, 0),
+                    },
+                    Port {
+                        name: "Rect".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::Rect(0, 0, 0, 0),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Result".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                ],
+            ),
+            // RectUnion - Smallest rect containing both rects
+            NodeType::RectUnion => (
+                vec![
+                    Port {
+                        name: "A".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::Rect(0, 0, 0, 0),
+                    },
+                    Port {
+                        name: "B".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::Rect(0, 0, 0, 0),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Rect".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::Rect(0, 0, 0, 0),
+                    },
+                ],
+            ),
+            // Distance - Straight-line distance between two points
+            NodeType::Distance => (
+                vec![
+                    Port {
+                        name: "A".into(),
+                        data_type: DataType::Point,
+                        default_value: VariableValue::Point(0, 0),
+                    },
+                    Port {
+                        name: "B".into(),
+                        data_type: DataType::Point,
+                        default_value: VariableValue::Point(0, 0),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Distance".into(),
+                        data_type: DataType::Float,
+                        default_value: VariableValue::Float(0.0),
+                    },
+                ],
+            ),
             // Xor
             NodeType::Xor => (
                 vec![
@@ -723,6 +944,129 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                     default_value: VariableValue::String("".into()),
                 }],
             ),
+            // RegexMatch - Whether the pattern matches anywhere in the text
+            NodeType::RegexMatch => (
+                vec![
+                    Port {
+                        name: "Text".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Pattern".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("\\d+".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Result".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                    Port {
+                        name: "ErrorMessage".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+            ),
+            // RegexCapture - Capture groups of the first match
+            NodeType::RegexCapture => (
+                vec![
+                    Port {
+                        name: "Text".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Pattern".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("\\d+".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Groups".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
+                    },
+                    Port {
+                        name: "Found".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                    Port {
+                        name: "ErrorMessage".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+            ),
+            // RegexFindAll - Every match of the pattern
+            NodeType::RegexFindAll => (
+                vec![
+                    Port {
+                        name: "Text".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Pattern".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("\\d+".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Matches".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
+                    },
+                    Port {
+                        name: "Count".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "ErrorMessage".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+            ),
+            // RegexReplace - Replace every match ($1, ${name} insert groups)
+            NodeType::RegexReplace => (
+                vec![
+                    Port {
+                        name: "Text".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Pattern".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("\\d+".into()),
+                    },
+                    Port {
+                        name: "Replacement".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Result".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "ErrorMessage".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+            ),
             // StringBetween - Extract content between two delimiter strings
             NodeType::StringBetween => (
                 vec![
@@ -1077,6 +1421,11 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         data_type: DataType::Integer,
                         default_value: VariableValue::Integer(0),
                     },
+                    Port {
+                        name: "Point".into(),
+                        data_type: DataType::Point,
+                        default_value: VariableValue::None,
+                    },
                 ],
                 vec![Port {
                     name: "Next".into(),
@@ -1152,6 +1501,11 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         data_type: DataType::Integer,
                         default_value: VariableValue::Integer(0),
                     },
+                    Port {
+                        name: "Point".into(),
+                        data_type: DataType::Point,
+                        default_value: VariableValue::None,
+                    },
                 ],
                 vec![Port {
                     name: "Next".into(),
@@ -1532,151 +1886,204 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                 }],
             ),
 
-            // JSONParse - Parse JSON string (pure function)
-            NodeType::JSONParse => (
-                vec![Port {
-                    name: "JSON".into(),
-                    data_type: DataType::String,
-                    default_value: VariableValue::String("{}".into()),
-                }],
-                vec![Port {
-                    name: "Value".into(),
-                    data_type: DataType::Custom("Any".into()),
-                    default_value: VariableValue::None,
-                }],
-            ),
-
-            // JSONStringify - Convert value to JSON string (pure function)
-            NodeType::JSONStringify => (
-                vec![Port {
-                    name: "Value".into(),
-                    data_type: DataType::Custom("Any".into()),
-                    default_value: VariableValue::None,
-                }],
-                vec![Port {
-                    name: "JSON".into(),
-                    data_type: DataType::String,
-                    default_value: VariableValue::String("".into()),
-                }],
-            ),
-
-            // HTTPRequest - Make HTTP request (execution flow)
-            NodeType::HTTPRequest => (
+            // ArraySort - Sorted copy, numbers by value and strings alphabetically
+            NodeType::ArraySort => (
                 vec![
                     Port {
-                        name: "In".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
+                        name: "Variable".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "URL".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("https://api.example.com".into()),
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
                     },
                     Port {
-                        name: "Method".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("GET".into()),
+                        name: "Numeric".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
                     },
                     Port {
-                        name: "Body".into(),
+                        name: "Descending".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Result".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
+                    },
+                ],
+            ),
+
+            // ArraySlice - Elements from Start up to End (negative counts from the end, End 0 = to the end)
+            NodeType::ArraySlice => (
+                vec![
+                    Port {
+                        name: "Variable".into(),
                         data_type: DataType::String,
                         default_value: VariableValue::String("".into()),
                     },
+                    Port {
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
+                    },
+                    Port {
+                        name: "Start".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "End".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
                 ],
                 vec![
                     Port {
-                        name: "Next".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
+                        name: "Result".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
                     },
+                ],
+            ),
+
+            // ArrayJoin - Elements joined into a string
+            NodeType::ArrayJoin => (
+                vec![
                     Port {
-                        name: "Response".into(),
+                        name: "Variable".into(),
                         data_type: DataType::String,
                         default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Success".into(),
-                        data_type: DataType::Boolean,
-                        default_value: VariableValue::Boolean(false),
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
+                    },
+                    Port {
+                        name: "Separator".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String(", ".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Result".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
                 ],
             ),
 
-            // Screenshot & Image Tools (Module C)
-            // ScreenCapture - Capture full screen or specific display
-            NodeType::ScreenCapture => (
+            // ArrayIndexOf - Index of the first equal element, -1 if none
+            NodeType::ArrayIndexOf => (
                 vec![
                     Port {
-                        name: "In".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
+                        name: "Variable".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Display".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
+                    },
+                    Port {
+                        name: "Value".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
                     },
                 ],
                 vec![
                     Port {
-                        name: "Next".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
+                        name: "Index".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(-1),
                     },
+                ],
+            ),
+
+            // ArrayContains - Whether an element equals Value
+            NodeType::ArrayContains => (
+                vec![
                     Port {
-                        name: "ImagePath".into(),
+                        name: "Variable".into(),
                         data_type: DataType::String,
                         default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Success".into(),
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
+                    },
+                    Port {
+                        name: "Value".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Result".into(),
                         data_type: DataType::Boolean,
                         default_value: VariableValue::Boolean(false),
                     },
                 ],
             ),
 
-            // SaveScreenshot - Save screenshot to file
-            NodeType::SaveScreenshot => (
+            // ArrayReverse - Copy in reverse order
+            NodeType::ArrayReverse => (
                 vec![
                     Port {
-                        name: "In".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
-                    },
-                    Port {
-                        name: "ImagePath".into(),
+                        name: "Variable".into(),
                         data_type: DataType::String,
                         default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Filename".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("screenshot.png".into()),
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
                     },
                 ],
                 vec![
                     Port {
-                        name: "Next".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
+                        name: "Result".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
                     },
+                ],
+            ),
+
+            // ArrayUnique - Copy without repeated elements, first occurrence kept
+            NodeType::ArrayUnique => (
+                vec![
                     Port {
-                        name: "SavedPath".into(),
+                        name: "Variable".into(),
                         data_type: DataType::String,
                         default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Success".into(),
-                        data_type: DataType::Boolean,
-                        default_value: VariableValue::Boolean(false),
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Result".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
                     },
                 ],
             ),
 
-            // RegionCapture - Capture a specific screen region to image
-            NodeType::RegionCapture => (
+            // ArrayRemoveAt - Remove the element at an index of an array variable (execution flow)
+            NodeType::ArrayRemoveAt => (
                 vec![
                     Port {
                         name: "In".into(),
@@ -1684,30 +2091,15 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "X".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Variable".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("myArray".into()),
                     },
                     Port {
-                        name: "Y".into(),
+                        name: "Index".into(),
                         data_type: DataType::Integer,
                         default_value: VariableValue::Integer(0),
                     },
-                    Port {
-                        name: "Width".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(200),
-                    },
-                    Port {
-                        name: "Height".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(100),
-                    },
-                    Port {
-                        name: "Filename".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("".into()),
-                    },
                 ],
                 vec![
                     Port {
@@ -1716,21 +2108,20 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "ImagePath".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("".into()),
+                        name: "Removed".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Success".into(),
-                        data_type: DataType::Boolean,
-                        default_value: VariableValue::Boolean(false),
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
                     },
                 ],
             ),
 
-            // Image Recognition (Module D)
-            // GetPixelColor - Get RGB color at screen coordinates
-            NodeType::GetPixelColor => (
+            // ArrayFilter - Keep the elements whose Keep input is true, evaluated per element after Body
+            NodeType::ArrayFilter => (
                 vec![
                     Port {
                         name: "In".into(),
@@ -1738,47 +2129,47 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "X".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
                     },
                     Port {
-                        name: "Y".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Keep".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(true),
                     },
                 ],
                 vec![
                     Port {
-                        name: "Next".into(),
+                        name: "Body".into(),
                         data_type: DataType::ExecutionFlow,
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "R".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Element".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "G".into(),
+                        name: "Index".into(),
                         data_type: DataType::Integer,
                         default_value: VariableValue::Integer(0),
                     },
                     Port {
-                        name: "B".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Done".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Success".into(),
-                        data_type: DataType::Boolean,
-                        default_value: VariableValue::Boolean(false),
+                        name: "Result".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
                     },
                 ],
             ),
 
-            // FindColor - Search for color in screen region
-            NodeType::FindColor => (
+            // ArrayMap - Replace each element by the Value input, evaluated per element after Body
+            NodeType::ArrayMap => (
                 vec![
                     Port {
                         name: "In".into(),
@@ -1786,61 +2177,116 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "R".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(255),
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
                     },
                     Port {
-                        name: "G".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Value".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
                     },
+                ],
+                vec![
                     Port {
-                        name: "B".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Body".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Tolerance".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(10),
+                        name: "Element".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "RegionX".into(),
+                        name: "Index".into(),
                         data_type: DataType::Integer,
                         default_value: VariableValue::Integer(0),
                     },
                     Port {
-                        name: "RegionY".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Done".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "RegionW".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(1920),
+                        name: "Result".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
+                    },
+                ],
+            ),
+
+            // JSONParse - Parse JSON string (pure function)
+            NodeType::JSONParse => (
+                vec![Port {
+                    name: "JSON".into(),
+                    data_type: DataType::String,
+                    default_value: VariableValue::String("{}".into()),
+                }],
+                vec![
+                    Port {
+                        name: "Value".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "RegionH".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(1080),
+                        name: "ErrorMessage".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
                 ],
+            ),
+
+            // JSONStringify - Convert value to JSON string (pure function)
+            NodeType::JSONStringify => (
+                vec![Port {
+                    name: "Value".into(),
+                    data_type: DataType::Custom("Any".into()),
+                    default_value: VariableValue::None,
+                }],
+                vec![Port {
+                    name: "JSON".into(),
+                    data_type: DataType::String,
+                    default_value: VariableValue::String("".into()),
+                }],
+            ),
+
+            // MapCreate - Creates an empty map
+            NodeType::MapCreate => (
+                vec![],
                 vec![
                     Port {
-                        name: "Next".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
+                        name: "Map".into(),
+                        data_type: DataType::Map,
+                        default_value: VariableValue::Map(Default::default()),
+                    },
+                ],
+            ),
+
+            // MapGet - Value for a key (supports variable name or direct map)
+            NodeType::MapGet => (
+                vec![
+                    Port {
+                        name: "Variable".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "X".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Map".into(),
+                        data_type: DataType::Map,
+                        default_value: VariableValue::Map(Default::default()),
                     },
                     Port {
-                        name: "Y".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Key".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Value".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
                     },
                     Port {
                         name: "Found".into(),
@@ -1850,8 +2296,8 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                 ],
             ),
 
-            // WaitForColor - Wait until color appears
-            NodeType::WaitForColor => (
+            // MapSet - Set a key of a map variable (execution flow)
+            NodeType::MapSet => (
                 vec![
                     Port {
                         name: "In".into(),
@@ -1859,39 +2305,19 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "R".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(255),
-                    },
-                    Port {
-                        name: "G".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
-                    },
-                    Port {
-                        name: "B".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
-                    },
-                    Port {
-                        name: "X".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
-                    },
-                    Port {
-                        name: "Y".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Variable".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("myMap".into()),
                     },
                     Port {
-                        name: "Tolerance".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(10),
+                        name: "Key".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Timeout".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(5000),
+                        name: "Value".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
                     },
                 ],
                 vec![
@@ -1901,15 +2327,15 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Found".into(),
-                        data_type: DataType::Boolean,
-                        default_value: VariableValue::Boolean(false),
+                        name: "Map".into(),
+                        data_type: DataType::Map,
+                        default_value: VariableValue::Map(Default::default()),
                     },
                 ],
             ),
 
-            // FindImage - Template matching on screen
-            NodeType::FindImage => (
+            // MapRemove - Remove a key from a map variable (execution flow)
+            NodeType::MapRemove => (
                 vec![
                     Port {
                         name: "In".into(),
@@ -1917,67 +2343,88 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "ImagePath".into(),
+                        name: "Variable".into(),
                         data_type: DataType::String,
-                        default_value: VariableValue::String("template.png".into()),
+                        default_value: VariableValue::String("myMap".into()),
                     },
                     Port {
-                        name: "Tolerance".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(10),
+                        name: "Key".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
+                ],
+                vec![
                     Port {
-                        name: "RegionX".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Next".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "RegionY".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Removed".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "RegionW".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(1920),
+                        name: "Map".into(),
+                        data_type: DataType::Map,
+                        default_value: VariableValue::Map(Default::default()),
                     },
+                ],
+            ),
+
+            // MapKeys - Keys of a map, sorted (supports variable name or direct map)
+            NodeType::MapKeys => (
+                vec![
                     Port {
-                        name: "RegionH".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(1080),
+                        name: "Variable".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Algorithm".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("NCC".into()),
+                        name: "Map".into(),
+                        data_type: DataType::Map,
+                        default_value: VariableValue::Map(Default::default()),
                     },
                 ],
                 vec![
                     Port {
-                        name: "Next".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
+                        name: "Keys".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
                     },
+                ],
+            ),
+
+            // MapHasKey - Whether a map has a key (supports variable name or direct map)
+            NodeType::MapHasKey => (
+                vec![
                     Port {
-                        name: "X".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Variable".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Y".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Map".into(),
+                        data_type: DataType::Map,
+                        default_value: VariableValue::Map(Default::default()),
                     },
                     Port {
-                        name: "Found".into(),
+                        name: "Key".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Result".into(),
                         data_type: DataType::Boolean,
                         default_value: VariableValue::Boolean(false),
                     },
                 ],
             ),
 
-            // WaitForImage - Wait until image appears on screen
-            NodeType::WaitForImage => (
+            // HTTPRequest - Make HTTP request (execution flow)
+            NodeType::HTTPRequest => (
                 vec![
                     Port {
                         name: "In".into(),
@@ -1985,19 +2432,19 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "ImagePath".into(),
+                        name: "URL".into(),
                         data_type: DataType::String,
-                        default_value: VariableValue::String("template.png".into()),
+                        default_value: VariableValue::String("https://api.example.com".into()),
                     },
                     Port {
-                        name: "Tolerance".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(10),
+                        name: "Method".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("GET".into()),
                     },
                     Port {
-                        name: "Timeout".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(5000),
+                        name: "Body".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
                 ],
                 vec![
@@ -2007,138 +2454,161 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "X".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
+                        name: "Response".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Y".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0),
-                    },
-                    Port {
-                        name: "Found".into(),
+                        name: "Success".into(),
                         data_type: DataType::Boolean,
                         default_value: VariableValue::Boolean(false),
                     },
                 ],
             ),
 
-            // ImageSimilarity - Compare two images with tolerance (pure function)
-            NodeType::ImageSimilarity => (
+            // Screenshot & Image Tools (Module C)
+            // ScreenCapture - Capture full screen or specific display
+            NodeType::ScreenCapture => (
                 vec![
                     Port {
-                        name: "ImagePath1".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("image1.png".into()),
-                    },
-                    Port {
-                        name: "ImagePath2".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("image2.png".into()),
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Tolerance".into(),
+                        name: "Display".into(),
                         data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(10),
+                        default_value: VariableValue::Integer(0),
                     },
                 ],
                 vec![
                     Port {
-                        name: "Similarity".into(),
-                        data_type: DataType::Float,
-                        default_value: VariableValue::Float(0.0),
+                        name: "Next".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Match".into(),
+                        name: "Image".into(),
+                        data_type: DataType::Image,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "ImagePath".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Success".into(),
                         data_type: DataType::Boolean,
                         default_value: VariableValue::Boolean(false),
                     },
                 ],
             ),
 
-            // Constant - Simple value passthrough
-            NodeType::Constant => (
-                vec![Port {
-                    name: "Value".into(),
-                    data_type: DataType::Float,
-                    default_value: VariableValue::Float(0.0),
-                }],
-                vec![Port {
-                    name: "Out".into(),
-                    data_type: DataType::Float,
-                    default_value: VariableValue::Float(0.0),
-                }],
-            ),
-
-            // ExtractAfter - Extract N characters after a keyword
-            NodeType::ExtractAfter => (
+            // SaveScreenshot - Save screenshot to file
+            NodeType::SaveScreenshot => (
                 vec![
                     Port {
-                        name: "Source".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("".into()),
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Keyword".into(),
+                        name: "Image".into(),
+                        data_type: DataType::Image,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "ImagePath".into(),
                         data_type: DataType::String,
                         default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Length".into(),
-                        data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(10),
+                        name: "Filename".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("screenshot.png".into()),
                     },
                 ],
                 vec![
                     Port {
-                        name: "Result".into(),
+                        name: "Next".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "SavedPath".into(),
                         data_type: DataType::String,
                         default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Found".into(),
+                        name: "Success".into(),
                         data_type: DataType::Boolean,
                         default_value: VariableValue::Boolean(false),
                     },
                 ],
             ),
 
-            // ExtractUntil - Extract content from keyword until delimiter
-            NodeType::ExtractUntil => (
+            // RegionCapture - Capture a specific screen region to image
+            NodeType::RegionCapture => (
                 vec![
                     Port {
-                        name: "Source".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("".into()),
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Keyword".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("".into()),
+                        name: "X".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
                     },
                     Port {
-                        name: "Delimiter".into(),
+                        name: "Y".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Width".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(200),
+                    },
+                    Port {
+                        name: "Height".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(100),
+                    },
+                    Port {
+                        name: "Filename".into(),
                         data_type: DataType::String,
-                        default_value: VariableValue::String(",".into()),
+                        default_value: VariableValue::String("".into()),
                     },
                 ],
                 vec![
                     Port {
-                        name: "Result".into(),
+                        name: "Next".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Image".into(),
+                        data_type: DataType::Image,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "ImagePath".into(),
                         data_type: DataType::String,
                         default_value: VariableValue::String("".into()),
                     },
                     Port {
-                        name: "Found".into(),
+                        name: "Success".into(),
                         data_type: DataType::Boolean,
                         default_value: VariableValue::Boolean(false),
                     },
                 ],
             ),
 
-            // WaitForCondition - Blocks until condition becomes true
-            NodeType::WaitForCondition => (
+            // Image Recognition (Module D)
+            // GetPixelColor - Get RGB color at screen (or Image) coordinates
+            NodeType::GetPixelColor => (
                 vec![
                     Port {
                         name: "In".into(),
@@ -2146,19 +2616,19 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Condition".into(),
-                        data_type: DataType::Boolean,
-                        default_value: VariableValue::Boolean(false),
+                        name: "Image".into(),
+                        data_type: DataType::Image,
+                        default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Poll Interval (ms)".into(),
+                        name: "X".into(),
                         data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(100),
+                        default_value: VariableValue::Integer(0),
                     },
                     Port {
-                        name: "Timeout (ms)".into(),
+                        name: "Y".into(),
                         data_type: DataType::Integer,
-                        default_value: VariableValue::Integer(0), // 0 = no timeout
+                        default_value: VariableValue::Integer(0),
                     },
                 ],
                 vec![
@@ -2168,15 +2638,30 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Timed Out".into(),
+                        name: "R".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "G".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "B".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Success".into(),
                         data_type: DataType::Boolean,
                         default_value: VariableValue::Boolean(false),
                     },
                 ],
             ),
 
-            // ForLoopAsync - For loop that waits for Continue signal before each iteration
-            NodeType::ForLoopAsync => (
+            // FindColor - Search for color in screen region
+            NodeType::FindColor => (
                 vec![
                     Port {
                         name: "In".into(),
@@ -2184,42 +2669,77 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Start".into(),
+                        name: "R".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(255),
+                    },
+                    Port {
+                        name: "G".into(),
                         data_type: DataType::Integer,
                         default_value: VariableValue::Integer(0),
                     },
                     Port {
-                        name: "End".into(),
+                        name: "B".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Tolerance".into(),
                         data_type: DataType::Integer,
                         default_value: VariableValue::Integer(10),
                     },
                     Port {
-                        name: "Continue".into(),
-                        data_type: DataType::ExecutionFlow,
+                        name: "RegionX".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "RegionY".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "RegionW".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(1920),
+                    },
+                    Port {
+                        name: "RegionH".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(1080),
+                    },
+                    Port {
+                        name: "Region".into(),
+                        data_type: DataType::Rect,
                         default_value: VariableValue::None,
                     },
                 ],
                 vec![
                     Port {
-                        name: "Loop".into(),
+                        name: "Next".into(),
                         data_type: DataType::ExecutionFlow,
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Index".into(),
+                        name: "X".into(),
                         data_type: DataType::Integer,
                         default_value: VariableValue::Integer(0),
                     },
                     Port {
-                        name: "Done".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
+                        name: "Y".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Found".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
                     },
                 ],
             ),
 
-            // ForEachLine - Iterate over each line in multi-line text
-            NodeType::ForEachLine => (
+            // WaitForColor - Wait until color appears
+            NodeType::WaitForColor => (
                 vec![
                     Port {
                         name: "In".into(),
@@ -2227,35 +2747,910 @@ pub fn get_ports_for_type(node_type: &NodeType) -> (Vec<Port>, Vec<Port>) {
                         default_value: VariableValue::None,
                     },
                     Port {
-                        name: "Text".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("".into()),
+                        name: "R".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(255),
                     },
-                ],
-                vec![
                     Port {
-                        name: "Loop".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
+                        name: "G".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
                     },
                     Port {
-                        name: "Line".into(),
-                        data_type: DataType::String,
-                        default_value: VariableValue::String("".into()),
+                        name: "B".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
                     },
                     Port {
-                        name: "Index".into(),
+                        name: "X".into(),
                         data_type: DataType::Integer,
                         default_value: VariableValue::Integer(0),
                     },
                     Port {
-                        name: "Done".into(),
-                        data_type: DataType::ExecutionFlow,
-                        default_value: VariableValue::None,
+                        name: "Y".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
                     },
-                ],
-            ),
-
-            _ => (vec![], vec![]),
-        }
-    }
+                    Port {
+                        name: "Tolerance".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(10),
+                    },
+                    Port {
+                        name: "Timeout".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(5000),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Next".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Found".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                ],
+            ),
+
+            // FindImage - Template matching on screen (or a Screen image)
+            NodeType::FindImage => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "ImagePath".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("template.png".into()),
+                    },
+                    Port {
+                        name: "Template".into(),
+                        data_type: DataType::Image,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Screen".into(),
+                        data_type: DataType::Image,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Tolerance".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(10),
+                    },
+                    Port {
+                        name: "RegionX".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "RegionY".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "RegionW".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(1920),
+                    },
+                    Port {
+                        name: "RegionH".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(1080),
+                    },
+                    Port {
+                        name: "Region".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Algorithm".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("NCC".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Next".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "X".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Y".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Found".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                    Port {
+                        name: "Match".into(),
+                        data_type: DataType::Rect,
+                        default_value: VariableValue::Rect(0, 0, 0, 0),
+                    },
+                ],
+            ),
+
+            // WaitForImage - Wait until image appears on screen
+            NodeType::WaitForImage => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "ImagePath".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("template.png".into()),
+                    },
+                    Port {
+                        name: "Template".into(),
+                        data_type: DataType::Image,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Tolerance".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(10),
+                    },
+                    Port {
+                        name: "Timeout".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(5000),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Next".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "X".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Y".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Found".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                ],
+            ),
+
+            // ImageSimilarity - Compare two images with tolerance (pure function)
+            NodeType::ImageSimilarity => (
+                vec![
+                    Port {
+                        name: "ImagePath1".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("image1.png".into()),
+                    },
+                    Port {
+                        name: "ImagePath2".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("image2.png".into()),
+                    },
+                    Port {
+                        name: "Image1".into(),
+                        data_type: DataType::Image,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Image2".into(),
+                        data_type: DataType::Image,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Tolerance".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(10),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Similarity".into(),
+                        data_type: DataType::Float,
+                        default_value: VariableValue::Float(0.0),
+                    },
+                    Port {
+                        name: "Match".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                ],
+            ),
+
+            // Constant - Simple value passthrough
+            NodeType::Constant => (
+                vec![Port {
+                    name: "Value".into(),
+                    data_type: DataType::Float,
+                    default_value: VariableValue::Float(0.0),
+                }],
+                vec![Port {
+                    name: "Out".into(),
+                    data_type: DataType::Float,
+                    default_value: VariableValue::Float(0.0),
+                }],
+            ),
+
+            // ExtractAfter - Extract N characters after a keyword
+            NodeType::ExtractAfter => (
+                vec![
+                    Port {
+                        name: "Source".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Keyword".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Length".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(10),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Result".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Found".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                ],
+            ),
+
+            // ExtractUntil - Extract content from keyword until delimiter
+            NodeType::ExtractUntil => (
+                vec![
+                    Port {
+                        name: "Source".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Keyword".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Delimiter".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String(",".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Result".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Found".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                ],
+            ),
+
+            // WaitForCondition - Blocks until condition becomes true
+            NodeType::WaitForCondition => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Condition".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                    Port {
+                        name: "Poll Interval (ms)".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(100),
+                    },
+                    Port {
+                        name: "Timeout (ms)".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0), // 0 = no timeout
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Next".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Timed Out".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                ],
+            ),
+
+            // ForLoopAsync - For loop that waits for Continue signal before each iteration
+            NodeType::ForLoopAsync => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Start".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "End".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(10),
+                    },
+                    Port {
+                        name: "Continue".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Loop".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Index".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Done".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                ],
+            ),
+
+            // ForEach - Iterate over the elements of an array
+            NodeType::ForEach => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Array".into(),
+                        data_type: DataType::Array,
+                        default_value: VariableValue::Array(vec![]),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Loop".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Element".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Index".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Done".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                ],
+            ),
+
+            // ForEachLine - Iterate over each line in multi-line text
+            NodeType::ForEachLine => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Text".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Loop".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Line".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                    Port {
+                        name: "Index".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "Done".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                ],
+            ),
+
+            // TryCatch - Route failures in the Try body to Catch
+            NodeType::TryCatch => (
+                vec![Port {
+                    name: "In".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+                vec![
+                    Port {
+                        name: "Try".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Catch".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Completed".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "ErrorMessage".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+            ),
+
+            // Parallel - Start every branch at once on its own thread
+            NodeType::Parallel => (
+                vec![Port {
+                    name: "In".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+                vec![
+                    Port {
+                        name: "Branch 0".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Branch 1".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Branch 2".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                ],
+            ),
+            // Break / Continue / Return - End the chain, see `FlowRuntime::jump`
+            NodeType::Break | NodeType::Continue | NodeType::Return => (
+                vec![Port {
+                    name: "In".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+                vec![],
+            ),
+            // DoOnce - Pass the first time only, until Reset
+            NodeType::DoOnce => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Reset".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Start Closed".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                ],
+                vec![Port {
+                    name: "Completed".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+            ),
+            // DoN - Pass the first N times, until Reset
+            NodeType::DoN => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Reset".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "N".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(1),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Exit".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Counter".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                ],
+            ),
+            // FlipFlop - Alternate between A and B
+            NodeType::FlipFlop => (
+                vec![Port {
+                    name: "In".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+                vec![
+                    Port {
+                        name: "A".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "B".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Is A".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(true),
+                    },
+                ],
+            ),
+            // Retry - Re-run Body with backoff until it succeeds
+            NodeType::Retry => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Max Attempts".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(3),
+                    },
+                    Port {
+                        name: "Delay (ms)".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(500),
+                    },
+                    Port {
+                        name: "Backoff".into(),
+                        data_type: DataType::Float,
+                        default_value: VariableValue::Float(2.0),
+                    },
+                    Port {
+                        name: "Success".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(true),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Body".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Succeeded".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Failed".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Attempt".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                    Port {
+                        name: "ErrorMessage".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+            ),
+            // WithTimeout - Cancel Body after Timeout (ms)
+            NodeType::WithTimeout => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Timeout (ms)".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(5000),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Body".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Completed".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "TimedOut".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                ],
+            ),
+            // Switch - One exec output per case; cases auto-expand like StringJoin
+            NodeType::SwitchOnString | NodeType::SwitchOnInt => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    if matches!(node_type, NodeType::SwitchOnInt) {
+                        Port {
+                            name: "Value".into(),
+                            data_type: DataType::Integer,
+                            default_value: VariableValue::Integer(0),
+                        }
+                    } else {
+                        Port {
+                            name: "Value".into(),
+                            data_type: DataType::String,
+                            default_value: VariableValue::String("".into()),
+                        }
+                    },
+                    Port {
+                        name: "Case 0".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("".into()),
+                    },
+                ],
+                vec![
+                    Port {
+                        name: "Default".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Case 0".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                ],
+            ),
+            // Join - Continue once all (or any) Parallel branches arrive
+            NodeType::Join => (
+                vec![
+                    Port {
+                        name: "In".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Wait All".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(true),
+                    },
+                ],
+                vec![Port {
+                    name: "Next".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+            ),
+
+            // User functions - CallFunction / CallScript also get one port per
+            // parameter and result, see `BlueprintGraph::sync_function_calls`
+            // and `BlueprintGraph::sync_script_calls`
+            NodeType::Entry => (
+                vec![],
+                vec![Port {
+                    name: "Next".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+            ),
+            NodeType::InputParam { .. } => (
+                vec![],
+                vec![Port {
+                    name: "Value".into(),
+                    data_type: DataType::Custom("Any".into()),
+                    default_value: VariableValue::None,
+                }],
+            ),
+            NodeType::OutputParam { .. } => (
+                vec![Port {
+                    name: "Value".into(),
+                    data_type: DataType::Custom("Any".into()),
+                    default_value: VariableValue::None,
+                }],
+                vec![],
+            ),
+            NodeType::CallFunction { .. } | NodeType::CallScript { .. } => (
+                vec![Port {
+                    name: "In".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+                vec![Port {
+                    name: "Next".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+            ),
+
+            // Events - Entry points scheduled by the interpreter
+            NodeType::OnStart | NodeType::OnStop => (
+                vec![],
+                vec![Port {
+                    name: "Next".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+            ),
+            NodeType::OnInterval => (
+                vec![Port {
+                    name: "Interval (ms)".into(),
+                    data_type: DataType::Integer,
+                    default_value: VariableValue::Integer(1000),
+                }],
+                vec![
+                    Port {
+                        name: "Next".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Count".into(),
+                        data_type: DataType::Integer,
+                        default_value: VariableValue::Integer(0),
+                    },
+                ],
+            ),
+            NodeType::OnVariableChanged => (
+                vec![Port {
+                    name: "Variable".into(),
+                    data_type: DataType::String,
+                    default_value: VariableValue::String("".into()),
+                }],
+                vec![
+                    Port {
+                        name: "Next".into(),
+                        data_type: DataType::ExecutionFlow,
+                        default_value: VariableValue::None,
+                    },
+                    Port {
+                        name: "Value".into(),
+                        data_type: DataType::Custom("Any".into()),
+                        default_value: VariableValue::None,
+                    },
+                ],
+            ),
+            NodeType::OnHotkey => (
+                vec![
+                    Port {
+                        name: "Key".into(),
+                        data_type: DataType::String,
+                        default_value: VariableValue::String("f6".into()),
+                    },
+                    Port {
+                        name: "Ctrl".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                    Port {
+                        name: "Shift".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                    Port {
+                        name: "Alt".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                    Port {
+                        name: "Command".into(),
+                        data_type: DataType::Boolean,
+                        default_value: VariableValue::Boolean(false),
+                    },
+                ],
+                vec![Port {
+                    name: "Next".into(),
+                    data_type: DataType::ExecutionFlow,
+                    default_value: VariableValue::None,
+                }],
+            ),
+
+            _ => (vec![], vec![]),
+        };
+
+    if node_type.has_error_flow() {
+        outputs.push(Port {
+            name: "Error".into(),
+            data_type: DataType::ExecutionFlow,
+            default_value: VariableValue::None,
+        });
+        outputs.push(Port {
+            name: "ErrorMessage".into(),
+            data_type: DataType::String,
+            default_value: VariableValue::String("".into()),
+        });
+    }
+
+    (inputs, outputs)
+}
@@ -29,7 +29,11 @@ use egui::{Color32, Pos2, Stroke};
 /// - Integer: Light Blue
 /// - String: Khaki
 /// - Vector3: Yellow
+/// - Point: Gold
+/// - Rect: Purple
 /// - Array: Orange
+/// - Map: Teal
+/// - Image: Pink
 /// - Custom: Gray
 pub fn get_type_color(dt: &DataType) -> Color32 {
     match dt {
@@ -39,7 +43,11 @@ pub fn get_type_color(dt: &DataType) -> Color32 {
         DataType::Integer => Color32::LIGHT_BLUE,
         DataType::String => Color32::KHAKI,
         DataType::Vector3 => Color32::YELLOW,
+        DataType::Point => Color32::GOLD,
+        DataType::Rect => Color32::from_rgb(160, 100, 255), // Purple
         DataType::Array => Color32::from_rgb(255, 165, 0), // Orange
+        DataType::Map => Color32::from_rgb(0, 180, 170),   // Teal
+        DataType::Image => Color32::from_rgb(255, 105, 180), // Pink
         DataType::Custom(_) => Color32::GRAY,
     }
 }
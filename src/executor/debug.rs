@@ -0,0 +1,153 @@
+//! # Debug Control
+//!
+//! Breakpoints, pause and single-step support for the interpreter.
+//!
+//! The UI keeps a [`Sender<DebugCommand>`] and the flow threads share a
+//! [`DebugControl`] through [`ExecutionContext`](super::ExecutionContext).
+//! Before every flow node the interpreter calls [`DebugControl::before_node`],
+//! which blocks while execution is paused.
+//!
+//! ## Stepping
+//! - **Continue**: run until the next breakpoint
+//! - **Step Over**: pause at the next node at the same or a shallower depth
+//!   (loop bodies and function / script calls run without pausing)
+//! - **Step Into**: pause at the very next node, including loop bodies
+
+use super::events::ExecutionEvent;
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, channel};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Commands sent from the UI to a running graph.
+#[derive(Debug, Clone)]
+pub enum DebugCommand {
+    /// Pause before the next flow node
+    Pause,
+    /// Resume until the next breakpoint
+    Continue,
+    /// Resume and pause at the next node on the same (or outer) level
+    StepOver,
+    /// Resume and pause at the very next node, entering loop bodies
+    StepInto,
+    /// Replace the breakpoint set
+    SetBreakpoints(HashSet<Uuid>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StepMode {
+    Run,
+    StepOver(usize),
+    StepInto,
+}
+
+thread_local! {
+    /// Loop-body nesting depth of the current flow thread (0 = top level)
+    static FLOW_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Increments the flow depth for the lifetime of a loop body.
+pub struct DepthGuard;
+
+impl DepthGuard {
+    pub fn enter() -> Self {
+        FLOW_DEPTH.with(|d| d.set(d.get() + 1));
+        DepthGuard
+    }
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        FLOW_DEPTH.with(|d| d.set(d.get().saturating_sub(1)));
+    }
+}
+
+/// Shared debugger state for one run.
+pub struct DebugControl {
+    breakpoints: Mutex<HashSet<Uuid>>,
+    pause_requested: AtomicBool,
+    mode: Mutex<StepMode>,
+    /// Only the thread currently paused (or polling) holds this lock
+    commands: Mutex<Receiver<DebugCommand>>,
+}
+
+impl DebugControl {
+    /// Create the control and the sender the UI uses to drive it.
+    pub fn new() -> (Arc<Self>, Sender<DebugCommand>) {
+        let (tx, rx) = channel();
+        let control = Arc::new(Self {
+            breakpoints: Mutex::new(HashSet::new()),
+            pause_requested: AtomicBool::new(false),
+            mode: Mutex::new(StepMode::Run),
+            commands: Mutex::new(rx),
+        });
+        (control, tx)
+    }
+
+    pub fn set_breakpoints(&self, breakpoints: HashSet<Uuid>) {
+        *self.breakpoints.lock().unwrap() = breakpoints;
+    }
+
+    fn apply(&self, cmd: DebugCommand, depth: usize) {
+        match cmd {
+            DebugCommand::Pause => self.pause_requested.store(true, Ordering::Relaxed),
+            DebugCommand::Continue => *self.mode.lock().unwrap() = StepMode::Run,
+            DebugCommand::StepOver => *self.mode.lock().unwrap() = StepMode::StepOver(depth),
+            DebugCommand::StepInto => *self.mode.lock().unwrap() = StepMode::StepInto,
+            DebugCommand::SetBreakpoints(set) => self.set_breakpoints(set),
+        }
+    }
+
+    fn should_pause(&self, node_id: Uuid, depth: usize) -> bool {
+        if self.pause_requested.swap(false, Ordering::Relaxed) {
+            return true;
+        }
+        let hit = match *self.mode.lock().unwrap() {
+            StepMode::Run => false,
+            StepMode::StepOver(d) => depth <= d,
+            StepMode::StepInto => true,
+        };
+        hit || self.breakpoints.lock().unwrap().contains(&node_id)
+    }
+
+    /// Called before each flow node. Applies pending commands and blocks while
+    /// paused. Returns `false` if stop was requested while waiting.
+    pub fn before_node(&self, node_id: Uuid, stop: &AtomicBool, tx: &Sender<ExecutionEvent>) -> bool {
+        let depth = FLOW_DEPTH.with(|d| d.get());
+        let commands = self.commands.lock().unwrap();
+
+        // Apply anything the UI sent while we were running
+        while let Ok(cmd) = commands.try_recv() {
+            self.apply(cmd, depth);
+        }
+
+        if !self.should_pause(node_id, depth) {
+            return true;
+        }
+
+        *self.mode.lock().unwrap() = StepMode::Run;
+        let _ = tx.send(ExecutionEvent::Paused(node_id));
+
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            match commands.recv_timeout(Duration::from_millis(50)) {
+                Ok(DebugCommand::SetBreakpoints(set)) => self.set_breakpoints(set),
+                Ok(DebugCommand::Pause) => {}
+                Ok(cmd) => {
+                    self.apply(cmd, depth);
+                    break;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        let _ = tx.send(ExecutionEvent::Resumed);
+        !stop.load(Ordering::Relaxed)
+    }
+}
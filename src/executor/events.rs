@@ -1,3 +1,4 @@
+use crate::graph::VariableValue;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -5,5 +6,16 @@ pub enum ExecutionEvent {
     Log(String),
     NodeActive(Uuid),
     NodeInactive(Uuid), // Optional, for finer control if needed later
+    /// A flow aborted because of an unrecoverable problem (missing entry point,
+    /// invalid input, step limit). Headless runs use this to set the exit code.
+    Error(String),
+    /// Execution is paused before this node (breakpoint or step)
+    Paused(Uuid),
+    /// Execution continued after a pause
+    Resumed,
+    /// A variable was written
+    VariableChanged(String, VariableValue),
+    /// A node output port was written (node, port, value)
+    OutputChanged(Uuid, String, VariableValue),
     Finished,
 }
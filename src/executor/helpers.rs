@@ -8,6 +8,8 @@
 //! - [`to_float`]: Convert any VariableValue to f64
 //! - [`to_string`]: Convert any VariableValue to String
 //! - [`compare_values`]: Compare two VariableValues
+//! - [`values_equal`]: Check two VariableValues for equality
+//! - [`to_point`] / [`to_rect`]: Read a VariableValue as a position or area
 //! - [`compute_math`]: Perform math operations preserving type
 //! - [`string_to_key`]: Convert string key name to enigo Key
 //!
@@ -67,6 +69,9 @@ pub fn to_float(val: &VariableValue) -> f64 {
 /// - Integer/Float/Boolean: to_string()
 /// - Vector3: "(x, y, z)" format
 /// - Array: "[item1, item2, ...]" format
+/// - Point: "(x, y)", Rect: "(x, y, WxH)"
+/// - Map: "{key: value, ...}" format
+/// - Image: "Image(WxH)"
 /// - None: "None"
 pub fn to_string(val: &VariableValue) -> String {
     match val {
@@ -75,10 +80,20 @@ pub fn to_string(val: &VariableValue) -> String {
         VariableValue::Float(f) => f.to_string(),
         VariableValue::Boolean(b) => b.to_string(),
         VariableValue::Vector3(x, y, z) => format!("({}, {}, {})", x, y, z),
+        VariableValue::Point(x, y) => format!("({}, {})", x, y),
+        VariableValue::Rect(x, y, w, h) => format!("({}, {}, {}x{})", x, y, w, h),
         VariableValue::Array(arr) => {
             let items: Vec<String> = arr.iter().map(|v| to_string(v)).collect();
             format!("[{}]", items.join(", "))
         }
+        VariableValue::Map(map) => {
+            let items: Vec<String> = map
+                .iter()
+                .map(|(k, v)| format!("{}: {}", k, to_string(v)))
+                .collect();
+            format!("{{{}}}", items.join(", "))
+        }
+        VariableValue::Image(image) => format!("Image({}x{})", image.0.width(), image.0.height()),
         VariableValue::None => "None".to_string(),
     }
 }
@@ -105,6 +120,80 @@ pub fn compare_values(a: &VariableValue, b: &VariableValue) -> std::cmp::Orderin
     }
 }
 
+/// Total order for sorting arrays of mixed values.
+///
+/// Values are grouped by type: None, booleans, numbers, strings, then the
+/// other types by name. Numbers compare by value across Integer and Float
+/// (NaN last), strings lexically. With `numeric`, strings holding a number
+/// sort as that number, so `"9"` comes before `"10"`.
+pub fn sort_order(a: &VariableValue, b: &VariableValue, numeric: bool) -> std::cmp::Ordering {
+    let number = |v: &VariableValue| match v {
+        VariableValue::Integer(i) => Some(*i as f64),
+        VariableValue::Float(f) => Some(*f),
+        VariableValue::String(s) if numeric => s.trim().parse::<f64>().ok(),
+        _ => None,
+    };
+    let rank = |v: &VariableValue| match v {
+        VariableValue::None => 0,
+        VariableValue::Boolean(_) => 1,
+        _ if number(v).is_some() => 2,
+        VariableValue::String(_) => 3,
+        _ => 4,
+    };
+
+    rank(a).cmp(&rank(b)).then_with(|| match (a, b) {
+        (VariableValue::Boolean(av), VariableValue::Boolean(bv)) => av.cmp(bv),
+        (VariableValue::String(av), VariableValue::String(bv)) if rank(a) == 3 => av.cmp(bv),
+        _ => match (number(a), number(b)) {
+            (Some(av), Some(bv)) => av.total_cmp(&bv),
+            _ => a.type_name().cmp(b.type_name()),
+        },
+    })
+}
+
+/// Check two VariableValues for equality.
+///
+/// Numbers are equal by value across Integer and Float; images only if they
+/// are the same image. Other values are equal if they have the same type and
+/// content.
+pub fn values_equal(a: &VariableValue, b: &VariableValue) -> bool {
+    match (a, b) {
+        (
+            VariableValue::Integer(_) | VariableValue::Float(_),
+            VariableValue::Integer(_) | VariableValue::Float(_),
+        ) => to_float(a) == to_float(b),
+        (VariableValue::Image(a), VariableValue::Image(b)) => std::sync::Arc::ptr_eq(&a.0, &b.0),
+        _ => {
+            a.type_name() == b.type_name()
+                && super::json_helpers::variable_value_to_json(a)
+                    == super::json_helpers::variable_value_to_json(b)
+        }
+    }
+}
+
+/// Convert a VariableValue to a point.
+///
+/// A Point is used as is and a Rect gives its center, so a found area can be
+/// clicked directly. Other values are not positions.
+pub fn to_point(val: &VariableValue) -> Option<(i64, i64)> {
+    match val {
+        VariableValue::Point(x, y) => Some((*x, *y)),
+        VariableValue::Rect(x, y, w, h) => Some((x + w / 2, y + h / 2)),
+        _ => None,
+    }
+}
+
+/// Convert a VariableValue to a rect `(x, y, width, height)`.
+///
+/// A Point is an empty rect at that position. Other values are not areas.
+pub fn to_rect(val: &VariableValue) -> Option<(i64, i64, i64, i64)> {
+    match val {
+        VariableValue::Rect(x, y, w, h) => Some((*x, *y, *w, *h)),
+        VariableValue::Point(x, y) => Some((*x, *y, 0, 0)),
+        _ => None,
+    }
+}
+
 /// Perform math operation on two VariableValues.
 ///
 /// Preserves integer type when both operands are integers,
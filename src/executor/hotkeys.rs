@@ -0,0 +1,183 @@
+//! # Global Hotkeys
+//!
+//! System-wide keyboard feed for OnHotkey event nodes.
+//!
+//! Only one `rdev::listen` hook can run per process, so the interpreter does
+//! not install its own. The host forwards keyboard events with [`publish`]:
+//! the GUI from its F3 stop listener, the headless runner through [`listen`].
+//! Running graphs read the feed with [`subscribe`].
+//!
+//! Key names are the ones accepted by keyboard nodes (`VALID_KEYS`) and are
+//! parsed with `string_to_key`, so `"f5"`, `"space"` or `"a"` mean the same
+//! key in HotKey and OnHotkey.
+
+use super::Interpreter;
+use enigo::Key;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+/// A key going down or up anywhere on the system
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyEvent {
+    Press(Key),
+    Release(Key),
+}
+
+static SUBSCRIBERS: Mutex<Vec<Sender<KeyEvent>>> = Mutex::new(Vec::new());
+
+/// Forward a global input event to every running graph. Non-keyboard events
+/// and keys without a name are ignored.
+pub fn publish(event: &rdev::EventType) {
+    let key_event = match event {
+        rdev::EventType::KeyPress(key) => key_from_rdev(*key).map(KeyEvent::Press),
+        rdev::EventType::KeyRelease(key) => key_from_rdev(*key).map(KeyEvent::Release),
+        _ => None,
+    };
+    if let Some(key_event) = key_event {
+        SUBSCRIBERS
+            .lock()
+            .unwrap()
+            .retain(|tx| tx.send(key_event).is_ok());
+    }
+}
+
+/// Receive every key event published from now on. Dropping the receiver
+/// unsubscribes.
+pub fn subscribe() -> Receiver<KeyEvent> {
+    let (tx, rx) = channel();
+    SUBSCRIBERS.lock().unwrap().push(tx);
+    rx
+}
+
+/// Install the global keyboard hook and publish its events. Blocks for the
+/// life of the process; call on a dedicated thread when no other hook runs.
+pub fn listen() -> Result<(), rdev::ListenError> {
+    rdev::listen(|event| publish(&event.event_type))
+}
+
+/// Key combination configured on an OnHotkey node
+#[derive(Debug, Clone)]
+pub struct Hotkey {
+    pub key: Key,
+    pub modifiers: HashSet<Key>,
+    /// As shown in logs, e.g. `"Ctrl+Shift+f5"`
+    pub label: String,
+}
+
+impl Hotkey {
+    /// Build from the node's `Key` name and modifier flags. `None` if the key
+    /// name is unknown.
+    pub fn new(key_name: &str, ctrl: bool, shift: bool, alt: bool, command: bool) -> Option<Self> {
+        let key = Interpreter::string_to_key(&key_name.trim().to_lowercase())?;
+
+        let mut modifiers = HashSet::new();
+        let mut label = Vec::new();
+        for (enabled, modifier, name) in [
+            (ctrl, Key::Control, "Ctrl"),
+            (shift, Key::Shift, "Shift"),
+            (alt, Key::Alt, "Alt"),
+            (command, Key::Meta, "Command"),
+        ] {
+            if enabled {
+                modifiers.insert(modifier);
+                label.push(name);
+            }
+        }
+        label.push(key_name.trim());
+
+        Some(Self {
+            key,
+            modifiers,
+            label: label.join("+"),
+        })
+    }
+
+    /// Whether pressing `key` while `held` keys are down triggers this hotkey.
+    /// Modifiers must match exactly, so `Ctrl+A` does not fire a plain `A`.
+    pub fn matches(&self, key: Key, held: &HashSet<Key>) -> bool {
+        key == self.key
+            && MODIFIERS
+                .iter()
+                .filter(|m| **m != key)
+                .all(|m| held.contains(m) == self.modifiers.contains(m))
+    }
+}
+
+const MODIFIERS: [Key; 4] = [Key::Control, Key::Shift, Key::Alt, Key::Meta];
+
+/// Map an rdev key to the enigo key of the same name
+fn key_from_rdev(key: rdev::Key) -> Option<Key> {
+    use rdev::Key as K;
+    let name = match key {
+        K::ShiftLeft | K::ShiftRight => "shift",
+        K::ControlLeft | K::ControlRight => "ctrl",
+        K::Alt | K::AltGr => "alt",
+        K::MetaLeft | K::MetaRight => "meta",
+        K::F1 => "f1",
+        K::F2 => "f2",
+        K::F3 => "f3",
+        K::F4 => "f4",
+        K::F5 => "f5",
+        K::F6 => "f6",
+        K::F7 => "f7",
+        K::F8 => "f8",
+        K::F9 => "f9",
+        K::F10 => "f10",
+        K::F11 => "f11",
+        K::F12 => "f12",
+        K::UpArrow => "up",
+        K::DownArrow => "down",
+        K::LeftArrow => "left",
+        K::RightArrow => "right",
+        K::Home => "home",
+        K::End => "end",
+        K::PageUp => "pageup",
+        K::PageDown => "pagedown",
+        K::Return | K::KpReturn => "return",
+        K::Escape => "escape",
+        K::Tab => "tab",
+        K::Backspace => "backspace",
+        K::Delete => "delete",
+        K::Space => "space",
+        K::CapsLock => "capslock",
+        K::KeyA => "a",
+        K::KeyB => "b",
+        K::KeyC => "c",
+        K::KeyD => "d",
+        K::KeyE => "e",
+        K::KeyF => "f",
+        K::KeyG => "g",
+        K::KeyH => "h",
+        K::KeyI => "i",
+        K::KeyJ => "j",
+        K::KeyK => "k",
+        K::KeyL => "l",
+        K::KeyM => "m",
+        K::KeyN => "n",
+        K::KeyO => "o",
+        K::KeyP => "p",
+        K::KeyQ => "q",
+        K::KeyR => "r",
+        K::KeyS => "s",
+        K::KeyT => "t",
+        K::KeyU => "u",
+        K::KeyV => "v",
+        K::KeyW => "w",
+        K::KeyX => "x",
+        K::KeyY => "y",
+        K::KeyZ => "z",
+        K::Num0 | K::Kp0 => "0",
+        K::Num1 | K::Kp1 => "1",
+        K::Num2 | K::Kp2 => "2",
+        K::Num3 | K::Kp3 => "3",
+        K::Num4 | K::Kp4 => "4",
+        K::Num5 | K::Kp5 => "5",
+        K::Num6 | K::Kp6 => "6",
+        K::Num7 | K::Kp7 => "7",
+        K::Num8 | K::Kp8 => "8",
+        K::Num9 | K::Kp9 => "9",
+        _ => return None,
+    };
+    Interpreter::string_to_key(name)
+}
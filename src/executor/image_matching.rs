@@ -13,9 +13,79 @@
 //! - Screen capture is in physical pixels
 //! - Scale factor is auto-detected (physical_width / logical_width)
 //! - Output coordinates are converted back to logical pixels
+//!
+//! ## Image Inputs
+//! Image ports take an in-memory [`ImageValue`] or a path to an image file.
+//! Files are decoded once and reused until they change on disk; only the
+//! most recently used [`MAX_LOADED_IMAGES`] stay decoded.
 
+use crate::graph::{ImageValue, VariableValue};
+use anyhow::{Context, Result, bail};
 use image::{GrayImage, RgbaImage};
 use imageproc::template_matching::{find_extremes, match_template_parallel, MatchTemplateMethod};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Decoded image files kept for reuse; older ones are dropped first
+pub const MAX_LOADED_IMAGES: usize = 32;
+
+/// Image files already decoded, least recently used first
+static LOADED_IMAGES: Mutex<Vec<LoadedImage>> = Mutex::new(Vec::new());
+
+struct LoadedImage {
+    path: PathBuf,
+    /// Modification time of the file when it was read
+    modified: SystemTime,
+    image: ImageValue,
+}
+
+/// Image given to an image input: the image itself, or the image file at the
+/// path it holds
+pub fn image_from_value(value: &VariableValue) -> Result<ImageValue> {
+    match value {
+        VariableValue::Image(image) => Ok(image.clone()),
+        VariableValue::String(path) if !path.is_empty() => load_image(path),
+        VariableValue::String(_) | VariableValue::None => bail!("No image provided"),
+        other => bail!("Expected an image or a path, got {}", other.type_name()),
+    }
+}
+
+/// Decode the image file at `path`, reusing the last decode if the file has
+/// not changed since
+pub fn load_image(path: &str) -> Result<ImageValue> {
+    let path = PathBuf::from(path);
+    let modified = std::fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .with_context(|| format!("Cannot read {}", path.display()))?;
+    {
+        let mut loaded = LOADED_IMAGES.lock().unwrap();
+        if let Some(index) = loaded.iter().position(|l| l.path == path) {
+            let entry = loaded.remove(index);
+            if entry.modified == modified {
+                let image = entry.image.clone();
+                loaded.push(entry);
+                return Ok(image);
+            }
+        }
+    }
+
+    let image = ImageValue(Arc::new(
+        image::open(&path)
+            .with_context(|| format!("Cannot decode {}", path.display()))?
+            .to_rgba8(),
+    ));
+    let mut loaded = LOADED_IMAGES.lock().unwrap();
+    if loaded.len() >= MAX_LOADED_IMAGES {
+        loaded.remove(0);
+    }
+    loaded.push(LoadedImage {
+        path,
+        modified,
+        image: image.clone(),
+    });
+    Ok(image)
+}
 
 /// Matching algorithm selection
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -22,7 +22,7 @@ use crate::graph::VariableValue;
 /// - Number (f64) → Float
 /// - String → String
 /// - Array → Array (recursive conversion)
-/// - Object → String (JSON serialized)
+/// - Object → Map (recursive conversion)
 pub fn json_to_variable_value(value: &serde_json::Value) -> VariableValue {
     match value {
         serde_json::Value::Null => VariableValue::None,
@@ -44,10 +44,11 @@ pub fn json_to_variable_value(value: &serde_json::Value) -> VariableValue {
                 .collect();
             VariableValue::Array(values)
         }
-        serde_json::Value::Object(obj) => {
-            // Convert object to JSON string for now (can be extended later)
-            VariableValue::String(serde_json::to_string(obj).unwrap_or_default())
-        }
+        serde_json::Value::Object(obj) => VariableValue::Map(
+            obj.iter()
+                .map(|(k, v)| (k.clone(), json_to_variable_value(v)))
+                .collect(),
+        ),
     }
 }
 
@@ -61,6 +62,7 @@ pub fn json_to_variable_value(value: &serde_json::Value) -> VariableValue {
 /// - String → String
 /// - Vector3 → Array [x, y, z]
 /// - Array → Array (recursive conversion)
+/// - Map → Object (recursive conversion)
 pub fn variable_value_to_json(value: &VariableValue) -> serde_json::Value {
     match value {
         VariableValue::None => serde_json::Value::Null,
@@ -69,6 +71,10 @@ pub fn variable_value_to_json(value: &VariableValue) -> serde_json::Value {
         VariableValue::Float(f) => serde_json::json!(*f),
         VariableValue::String(s) => serde_json::Value::String(s.clone()),
         VariableValue::Vector3(x, y, z) => serde_json::json!([x, y, z]),
+        VariableValue::Point(x, y) => serde_json::json!({ "x": x, "y": y }),
+        VariableValue::Rect(x, y, w, h) => {
+            serde_json::json!({ "x": x, "y": y, "width": w, "height": h })
+        }
         VariableValue::Array(arr) => {
             let values: Vec<serde_json::Value> = arr
                 .iter()
@@ -76,5 +82,12 @@ pub fn variable_value_to_json(value: &VariableValue) -> serde_json::Value {
                 .collect();
             serde_json::Value::Array(values)
         }
+        VariableValue::Map(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), variable_value_to_json(v)))
+                .collect(),
+        ),
+        // Images have no JSON form
+        VariableValue::Image(_) => serde_json::Value::Null,
     }
 }
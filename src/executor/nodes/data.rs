@@ -0,0 +1,432 @@
+//! Data nodes with side effects: variables, printing, files, arrays, maps
+//! and HTTP.
+
+use super::{Flow, NodeExecutor};
+use crate::executor::Interpreter;
+use crate::executor::runtime::FlowRuntime;
+use crate::graph::{Node, VariableValue};
+use crate::node_types::NodeType;
+
+pub struct SetVariable;
+
+impl NodeExecutor for SetVariable {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let NodeType::SetVariable { name } = &node.node_type else {
+            return Flow::End;
+        };
+
+        // Check input
+        match rt.input(id, "Value") {
+            Ok(val) => rt.context.lock().unwrap().set_variable(name.clone(), val),
+            Err(e) => return rt.fail(id, format!("SetVariable: Error evaluating input: {}", e)),
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+/// `BlueprintFunction { name: "Print String" }`
+pub struct PrintString;
+
+impl NodeExecutor for PrintString {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        if let Ok(val) = rt.input(id, "String") {
+            // Auto-convert any type to string
+            rt.log(Interpreter::to_string(&val));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct FileWrite;
+
+impl NodeExecutor for FileWrite {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let path = rt.input(id, "Path")
+            .unwrap_or(VariableValue::String("".into()));
+        let content =
+            rt.input(id, "Content")
+                .unwrap_or(VariableValue::String("".into()));
+
+        let path_s = match path {
+            VariableValue::String(s) => s,
+            _ => "".to_string(),
+        };
+        let content_s = match content {
+            VariableValue::String(s) => s,
+            _ => "".to_string(),
+        };
+
+        match std::fs::write(&path_s, &content_s) {
+            Ok(_) => logger(format!("FileWrite: Successfully wrote to {}", path_s)),
+            Err(e) => {
+                return rt.fail(id, format!("FileWrite: Error writing to {}: {}", path_s, e));
+            }
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct ArrayPush;
+
+impl NodeExecutor for ArrayPush {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let var_name =
+            rt.input(id, "Variable")
+                .map(|v| Interpreter::to_string(&v))
+                .unwrap_or_default();
+        let value = rt.input(id, "Value")
+            .unwrap_or(VariableValue::None);
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            let pushed = ctx
+                .update_variable(&var_name, |v| match v {
+                    VariableValue::Array(arr) => {
+                        arr.push(value.clone());
+                        Some(arr.clone())
+                    }
+                    _ => None,
+                })
+                .flatten();
+            if let Some(arr_clone) = pushed {
+                // Store array for output port
+                ctx.set_output(id, "Array", VariableValue::Array(arr_clone));
+                logger(format!("ArrayPush: Added element to '{}'", var_name));
+            } else {
+                // Create new array if variable doesn't exist or isn't an array
+                let new_arr = vec![value];
+                ctx.set_variable(var_name.clone(), VariableValue::Array(new_arr.clone()));
+                ctx.set_output(id, "Array", VariableValue::Array(new_arr));
+                logger(format!("ArrayPush: Created new array '{}'", var_name));
+            }
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct ArrayPop;
+
+impl NodeExecutor for ArrayPop {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let var_name =
+            rt.input(id, "Variable")
+                .map(|v| Interpreter::to_string(&v))
+                .unwrap_or_default();
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            
+            // Handle ArrayPop - extract array, modify it, store results
+            let popped = ctx
+                .update_variable(&var_name, |v| match v {
+                    VariableValue::Array(arr) => Some((arr.pop(), arr.clone())),
+                    _ => None,
+                })
+                .flatten();
+            let (popped_value, arr_clone) = if let Some((popped, arr_clone)) = popped {
+                match popped {
+                    Some(v) => {
+                        logger(format!("ArrayPop: Removed element from '{}'", var_name));
+                        (v, arr_clone)
+                    },
+                    None => {
+                        logger(format!("ArrayPop: Array '{}' is empty", var_name));
+                        (VariableValue::None, arr_clone)
+                    }
+                }
+            } else {
+                logger(format!("ArrayPop: Variable '{}' is not an array", var_name));
+                (VariableValue::None, vec![])
+            };
+            
+            ctx.set_output(id, "Value", popped_value);
+            ctx.set_output(id, "Array", VariableValue::Array(arr_clone));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct ArraySet;
+
+impl NodeExecutor for ArraySet {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let var_name =
+            rt.input(id, "Variable")
+                .map(|v| Interpreter::to_string(&v))
+                .unwrap_or_default();
+        let index = rt.input(id, "Index")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as usize,
+                VariableValue::Float(f) => f as usize,
+                _ => 0,
+            })
+            .unwrap_or(0);
+        let value = rt.input(id, "Value")
+            .unwrap_or(VariableValue::None);
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            let updated = ctx
+                .update_variable(&var_name, |v| match v {
+                    VariableValue::Array(arr) => {
+                        if index < arr.len() {
+                            arr[index] = value;
+                            logger(format!("ArraySet: Set index {} of '{}'", index, var_name));
+                        } else {
+                            // Extend array if necessary
+                            while arr.len() <= index {
+                                arr.push(VariableValue::None);
+                            }
+                            arr[index] = value;
+                            logger(format!(
+                                "ArraySet: Extended '{}' and set index {}",
+                                var_name, index
+                            ));
+                        }
+                        Some(arr.clone())
+                    }
+                    _ => None,
+                })
+                .flatten();
+            if let Some(arr_clone) = updated {
+                // Store array for output port
+                ctx.set_output(id, "Array", VariableValue::Array(arr_clone));
+            } else {
+                ctx.set_output(id, "Array", VariableValue::Array(vec![]));
+                logger(format!("ArraySet: Variable '{}' is not an array", var_name));
+            }
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct ArrayRemoveAt;
+
+impl NodeExecutor for ArrayRemoveAt {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let var_name = rt
+            .input(id, "Variable")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        let index = rt
+            .input(id, "Index")
+            .map(|v| Interpreter::to_float(&v) as i64)
+            .unwrap_or(0);
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            let removed = ctx
+                .update_variable(&var_name, |v| match v {
+                    VariableValue::Array(arr) => {
+                        let removed = usize::try_from(index)
+                            .ok()
+                            .filter(|&i| i < arr.len())
+                            .map(|i| arr.remove(i));
+                        Some((removed, arr.clone()))
+                    }
+                    _ => None,
+                })
+                .flatten();
+            let (removed, arr) = match removed {
+                Some((Some(value), arr)) => {
+                    logger(format!("ArrayRemoveAt: Removed index {} from '{}'", index, var_name));
+                    (value, arr)
+                }
+                Some((None, arr)) => {
+                    logger(format!(
+                        "ArrayRemoveAt: Index {} out of range for '{}' (length {})",
+                        index,
+                        var_name,
+                        arr.len()
+                    ));
+                    (VariableValue::None, arr)
+                }
+                None => {
+                    logger(format!("ArrayRemoveAt: Variable '{}' is not an array", var_name));
+                    (VariableValue::None, vec![])
+                }
+            };
+            ctx.set_output(id, "Removed", removed);
+            ctx.set_output(id, "Array", VariableValue::Array(arr));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct MapSet;
+
+impl NodeExecutor for MapSet {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let var_name = rt
+            .input(id, "Variable")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        let key = rt
+            .input(id, "Key")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        let value = rt.input(id, "Value").unwrap_or(VariableValue::None);
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            let updated = ctx
+                .update_variable(&var_name, |v| match v {
+                    VariableValue::Map(map) => {
+                        map.insert(key.clone(), value.clone());
+                        Some(map.clone())
+                    }
+                    _ => None,
+                })
+                .flatten();
+            let map = match updated {
+                Some(map) => {
+                    logger(format!("MapSet: Set '{}' of '{}'", key, var_name));
+                    map
+                }
+                None => {
+                    // Create new map if variable doesn't exist or isn't a map
+                    let map = std::collections::BTreeMap::from([(key, value)]);
+                    ctx.set_variable(var_name.clone(), VariableValue::Map(map.clone()));
+                    logger(format!("MapSet: Created new map '{}'", var_name));
+                    map
+                }
+            };
+            ctx.set_output(id, "Map", VariableValue::Map(map));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct MapRemove;
+
+impl NodeExecutor for MapRemove {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let var_name = rt
+            .input(id, "Variable")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        let key = rt
+            .input(id, "Key")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            let removed = ctx
+                .update_variable(&var_name, |v| match v {
+                    VariableValue::Map(map) => Some((map.remove(&key), map.clone())),
+                    _ => None,
+                })
+                .flatten();
+            let (removed, map) = match removed {
+                Some((Some(value), map)) => {
+                    logger(format!("MapRemove: Removed '{}' from '{}'", key, var_name));
+                    (value, map)
+                }
+                Some((None, map)) => {
+                    logger(format!("MapRemove: '{}' has no key '{}'", var_name, key));
+                    (VariableValue::None, map)
+                }
+                None => {
+                    logger(format!("MapRemove: Variable '{}' is not a map", var_name));
+                    (VariableValue::None, Default::default())
+                }
+            };
+            ctx.set_output(id, "Removed", removed);
+            ctx.set_output(id, "Map", VariableValue::Map(map));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct HTTPRequest;
+
+impl NodeExecutor for HTTPRequest {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let url = rt.input(id, "URL")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        let method = rt.input(id, "Method")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_else(|_| "GET".to_string());
+        let body = rt.input(id, "Body")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+
+        logger(format!("HTTPRequest: {} {}", method.to_uppercase(), url));
+
+        // Simple synchronous HTTP using std::process::Command with curl
+        // Note: For production, consider using reqwest crate with async
+        let result = if method.to_uppercase() == "POST" {
+            std::process::Command::new("curl")
+                .args(["-s", "-X", "POST", "-d", &body, &url])
+                .output()
+        } else {
+            std::process::Command::new("curl")
+                .args(["-s", &url])
+                .output()
+        };
+
+        let mut error = None;
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            match result {
+                Ok(output) => {
+                    let response = String::from_utf8_lossy(&output.stdout).to_string();
+                    ctx.set_output(id, "Response", VariableValue::String(response.clone()));
+                    ctx.set_output(id, "Success", VariableValue::Boolean(output.status.success()));
+                    logger(format!(
+                        "HTTPRequest: Response received ({} bytes)",
+                        response.len()
+                    ));
+                }
+                Err(e) => {
+                    ctx.set_output(id, "Response", VariableValue::String("".into()));
+                    ctx.set_output(id, "Success", VariableValue::Boolean(false));
+                    error = Some(format!("HTTPRequest: Error - {}", e));
+                }
+            }
+        }
+
+        if let Some(message) = error {
+            return rt.fail(id, message);
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
@@ -0,0 +1,720 @@
+//! Flow control nodes: branches, loops, sequences, parallel branches, waits
+//! and error handling.
+
+use super::{Flow, NodeExecutor};
+use crate::executor::Interpreter;
+use crate::executor::runtime::{FlowRuntime, ForkScope, Jump};
+use crate::graph::{Node, VariableValue};
+use std::thread;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub struct Branch;
+
+impl NodeExecutor for Branch {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let condition = rt
+            .input(id, "Condition")
+            .unwrap_or(VariableValue::Boolean(false));
+
+        let bool_val = match condition {
+            VariableValue::Boolean(b) => b,
+            VariableValue::Integer(i) => i > 0,
+            VariableValue::Float(f) => f > 0.0,
+            _ => false,
+        };
+
+        let port = if bool_val { "True" } else { "False" };
+        rt.flow_to(id, port)
+    }
+}
+
+pub struct SwitchOnString;
+
+impl NodeExecutor for SwitchOnString {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let value = rt
+            .input(node.id, "Value")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        switch(rt, node, |case| case == value)
+    }
+}
+
+pub struct SwitchOnInt;
+
+impl NodeExecutor for SwitchOnInt {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let value = match rt.input(node.id, "Value") {
+            Ok(VariableValue::Integer(i)) => Some(i),
+            Ok(VariableValue::Float(f)) => Some(f as i64),
+            Ok(VariableValue::String(s)) => s.trim().parse().ok(),
+            _ => None,
+        };
+        switch(rt, node, |case| value.is_some() && case.trim().parse().ok() == value)
+    }
+}
+
+/// Follow the first non-empty `Case N` input that `matches`, else `Default`
+fn switch(rt: &mut FlowRuntime, node: &Node, matches: impl Fn(&str) -> bool) -> Flow {
+    let id = node.id;
+    for port in node.inputs.iter().filter(|p| p.name.starts_with("Case ")) {
+        let case = rt
+            .input(id, &port.name)
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        if !case.is_empty() && matches(&case) {
+            return rt.flow_to(id, &port.name);
+        }
+    }
+    rt.flow_to(id, "Default")
+}
+
+pub struct Delay;
+
+impl NodeExecutor for Delay {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let duration_ms = match rt.input(id, "Duration (ms)") {
+            Ok(VariableValue::Integer(ms)) => ms as u64,
+            Ok(VariableValue::Float(ms)) => ms as u64,
+            _ => 1000,
+        };
+        rt.log(format!("Delay: Sleeping for {}ms", duration_ms));
+        rt.sleep(Duration::from_millis(duration_ms));
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct ForLoop;
+
+impl NodeExecutor for ForLoop {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let start = match rt.input(id, "Start") {
+            Ok(VariableValue::Integer(i)) => i,
+            _ => 0,
+        };
+        let end = match rt.input(id, "End") {
+            Ok(VariableValue::Integer(i)) => i,
+            _ => 10,
+        };
+
+        // Execute Loop body for each iteration
+        for i in start..end {
+            if rt.loop_interrupted("ForLoop") {
+                break;
+            }
+
+            rt.context
+                .lock()
+                .unwrap()
+                .set_output(id, "Index", VariableValue::Integer(i));
+
+            if rt.run_loop_body(id, "Loop") == Some(Jump::Break) {
+                break;
+            }
+        }
+
+        // Continue to Done
+        rt.flow_to(id, "Done")
+    }
+}
+
+pub struct WhileLoop;
+
+impl NodeExecutor for WhileLoop {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let max_iterations = 1000; // Safety limit
+        let mut iteration = 0;
+
+        while iteration < max_iterations {
+            if rt.loop_interrupted("WhileLoop") {
+                // Stopped, end execution entirely
+                return Flow::End;
+            }
+
+            let condition = rt
+                .input(id, "Condition")
+                .unwrap_or(VariableValue::Boolean(false));
+
+            if !Interpreter::to_bool(&condition) {
+                break;
+            }
+
+            iteration += 1;
+            if rt.run_loop_body(id, "Loop") == Some(Jump::Break) {
+                break;
+            }
+        }
+
+        // Continue to Done
+        rt.flow_to(id, "Done")
+    }
+}
+
+pub struct Sequence;
+
+impl NodeExecutor for Sequence {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        // Execute each output in order
+        for i in 0..3 {
+            rt.run_body(node.id, &format!("Then {}", i));
+        }
+        // Sequence node doesn't continue to a "Next", it just ends
+        Flow::End
+    }
+}
+
+pub struct Parallel;
+
+impl NodeExecutor for Parallel {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let branches: Vec<_> = (0..3)
+            .filter_map(|i| rt.follow(id, &format!("Branch {}", i)))
+            .collect();
+        if branches.is_empty() {
+            return Flow::End;
+        }
+
+        rt.log(format!("Parallel: Starting {} branches", branches.len()));
+        let scope = ForkScope::new(branches.len());
+        let handles: Vec<_> = branches
+            .into_iter()
+            .map(|start| {
+                let branch = rt.branch(scope.clone());
+                thread::spawn(move || branch.run_branch(start))
+            })
+            .collect();
+
+        // Branches the Join or a stop cancelled end at their next node; none
+        // may outlive the Parallel node
+        let joined = scope.wait(|| rt.should_stop());
+        let errors: Vec<String> = handles
+            .into_iter()
+            .filter_map(|handle| handle.join().ok().flatten())
+            .collect();
+        if let Some(message) = errors.into_iter().next() {
+            return rt.fail(id, message);
+        }
+
+        // Continue after the Join the branches lead to, like Sequence ends
+        // when they don't lead to one
+        match joined {
+            Some(join_id) => rt.flow_to(join_id, "Next"),
+            None => Flow::End,
+        }
+    }
+}
+
+pub struct Join;
+
+impl NodeExecutor for Join {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        match rt.fork() {
+            // The Parallel node continues from here once the branches allow it
+            Some(scope) => {
+                let wait_all = rt
+                    .input(id, "Wait All")
+                    .map(|v| Interpreter::to_bool(&v))
+                    .unwrap_or(true);
+                scope.arrive(id, wait_all);
+                Flow::End
+            }
+            // Not inside a Parallel branch: nothing to wait for
+            None => rt.flow_to(id, "Next"),
+        }
+    }
+}
+
+pub struct Gate;
+
+impl NodeExecutor for Gate {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let is_open = rt.input(id, "Open").unwrap_or(VariableValue::Boolean(true));
+
+        if Interpreter::to_bool(&is_open) {
+            rt.flow_to(id, "Out")
+        } else {
+            // Gate is closed, stop execution
+            Flow::End
+        }
+    }
+}
+
+pub struct DoOnce;
+
+impl NodeExecutor for DoOnce {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let start_closed = rt
+            .input(id, "Start Closed")
+            .map(|v| Interpreter::to_bool(&v))
+            .unwrap_or(false);
+
+        // State: whether the node has closed since the last Reset
+        let open = rt.context.lock().unwrap().update_node_state(id, |state| {
+            let closed = state.get_or_insert(VariableValue::Boolean(start_closed));
+            let open = !Interpreter::to_bool(closed);
+            *closed = VariableValue::Boolean(true);
+            open
+        });
+
+        if open {
+            rt.flow_to(id, "Completed")
+        } else {
+            Flow::End
+        }
+    }
+}
+
+pub struct DoN;
+
+impl NodeExecutor for DoN {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let n = match rt.input(id, "N") {
+            Ok(VariableValue::Integer(n)) => n,
+            Ok(VariableValue::Float(n)) => n as i64,
+            _ => 1,
+        };
+
+        // State: number of times the node has passed since the last Reset
+        let counter = rt.context.lock().unwrap().update_node_state(id, |state| {
+            let count = match state {
+                Some(VariableValue::Integer(count)) => *count,
+                _ => 0,
+            };
+            if count >= n {
+                return None;
+            }
+            *state = Some(VariableValue::Integer(count + 1));
+            Some(count + 1)
+        });
+
+        match counter {
+            Some(counter) => {
+                rt.context
+                    .lock()
+                    .unwrap()
+                    .set_output(id, "Counter", VariableValue::Integer(counter));
+                rt.flow_to(id, "Exit")
+            }
+            None => Flow::End,
+        }
+    }
+}
+
+pub struct FlipFlop;
+
+impl NodeExecutor for FlipFlop {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+
+        // State: whether the next pass goes to A
+        let is_a = {
+            let mut ctx = rt.context.lock().unwrap();
+            let is_a = ctx.update_node_state(id, |state| {
+                let is_a = !matches!(state, Some(VariableValue::Boolean(false)));
+                *state = Some(VariableValue::Boolean(!is_a));
+                is_a
+            });
+            ctx.set_output(id, "Is A", VariableValue::Boolean(is_a));
+            is_a
+        };
+
+        rt.flow_to(id, if is_a { "A" } else { "B" })
+    }
+}
+
+pub struct Retry;
+
+impl NodeExecutor for Retry {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let max_attempts = match rt.input(id, "Max Attempts") {
+            Ok(VariableValue::Integer(n)) => n.max(1),
+            Ok(VariableValue::Float(n)) => (n as i64).max(1),
+            _ => 3,
+        };
+        let mut delay_ms = match rt.input(id, "Delay (ms)") {
+            Ok(VariableValue::Integer(ms)) => ms.max(0) as f64,
+            Ok(VariableValue::Float(ms)) => ms.max(0.0),
+            _ => 500.0,
+        };
+        let backoff = match rt.input(id, "Backoff") {
+            Ok(VariableValue::Float(f)) => f.max(1.0),
+            Ok(VariableValue::Integer(i)) => (i as f64).max(1.0),
+            _ => 2.0,
+        };
+
+        for attempt in 1..=max_attempts {
+            if rt.loop_interrupted("Retry") {
+                return Flow::End;
+            }
+            rt.context
+                .lock()
+                .unwrap()
+                .set_output(id, "Attempt", VariableValue::Integer(attempt));
+
+            // A failure in the body fails the attempt, as does a false Success
+            let error = rt.run_try(id, "Body");
+            if rt.should_stop() {
+                return Flow::End;
+            }
+            let error = error.or_else(|| {
+                let success = rt
+                    .input(id, "Success")
+                    .map(|v| Interpreter::to_bool(&v))
+                    .unwrap_or(false);
+                (!success).then(|| "Success was false".to_string())
+            });
+            let Some(error) = error else {
+                rt.log(format!("Retry: Succeeded on attempt {}", attempt));
+                return rt.flow_to(id, "Succeeded");
+            };
+            rt.context
+                .lock()
+                .unwrap()
+                .set_output(id, "ErrorMessage", VariableValue::String(error.clone()));
+            if attempt == max_attempts {
+                break;
+            }
+
+            rt.log(format!(
+                "Retry: Attempt {} of {} failed ({}), retrying in {}ms",
+                attempt, max_attempts, error, delay_ms as u64
+            ));
+            rt.sleep(Duration::from_millis(delay_ms as u64));
+            delay_ms *= backoff;
+        }
+
+        rt.log(format!("Retry: Gave up after {} attempts", max_attempts));
+        rt.flow_to(id, "Failed")
+    }
+}
+
+pub struct WithTimeout;
+
+impl NodeExecutor for WithTimeout {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let timeout_ms = match rt.input(id, "Timeout (ms)") {
+            Ok(VariableValue::Integer(ms)) => ms.max(0) as u64,
+            Ok(VariableValue::Float(ms)) => ms.max(0.0) as u64,
+            _ => 5000,
+        };
+
+        let timed_out = rt.run_body_with_timeout(id, "Body", Duration::from_millis(timeout_ms));
+        if rt.should_stop() {
+            return Flow::End;
+        }
+        if timed_out {
+            rt.log(format!("WithTimeout: Body cancelled after {}ms", timeout_ms));
+            rt.flow_to(id, "TimedOut")
+        } else {
+            rt.flow_to(id, "Completed")
+        }
+    }
+}
+
+pub struct WaitForCondition;
+
+impl NodeExecutor for WaitForCondition {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let poll_interval = match rt.input(id, "Poll Interval (ms)") {
+            Ok(VariableValue::Integer(ms)) => ms as u64,
+            Ok(VariableValue::Float(ms)) => ms as u64,
+            _ => 100,
+        };
+        let timeout_ms = match rt.input(id, "Timeout (ms)") {
+            Ok(VariableValue::Integer(ms)) => ms as u64,
+            Ok(VariableValue::Float(ms)) => ms as u64,
+            _ => 0,
+        };
+
+        let start_time = std::time::Instant::now();
+        let mut timed_out = false;
+
+        rt.log("WaitForCondition: Waiting for condition...".to_string());
+
+        loop {
+            if rt.loop_interrupted("WaitForCondition") {
+                break;
+            }
+
+            // Check condition
+            let condition = rt
+                .input(id, "Condition")
+                .unwrap_or(VariableValue::Boolean(false));
+            if Interpreter::to_bool(&condition) {
+                rt.log("WaitForCondition: Condition met!".to_string());
+                break;
+            }
+
+            // Check timeout (0 = no timeout)
+            if timeout_ms > 0 && start_time.elapsed().as_millis() as u64 > timeout_ms {
+                timed_out = true;
+                rt.log(format!("WaitForCondition: Timed out after {}ms", timeout_ms));
+                break;
+            }
+
+            rt.sleep(Duration::from_millis(poll_interval));
+        }
+
+        // Store timed_out result for output port
+        rt.context
+            .lock()
+            .unwrap()
+            .set_output(id, "Timed Out", VariableValue::Boolean(timed_out));
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct ForLoopAsync;
+
+impl NodeExecutor for ForLoopAsync {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let start = match rt.input(id, "Start") {
+            Ok(VariableValue::Integer(i)) => i,
+            _ => 0,
+        };
+        let end = match rt.input(id, "End") {
+            Ok(VariableValue::Integer(i)) => i,
+            _ => 10,
+        };
+
+        for i in start..end {
+            if rt.loop_interrupted("ForLoopAsync") {
+                break;
+            }
+
+            // Set Index output and clear the continue signal (node state)
+            {
+                let mut ctx = rt.context.lock().unwrap();
+                ctx.set_output(id, "Index", VariableValue::Integer(i));
+                ctx.update_node_state(id, |state| *state = None);
+            }
+
+            rt.log(format!("ForLoopAsync: Starting iteration {} of {}", i, end - 1));
+
+            // The body ends by flowing into our Continue port, which sets the
+            // signal, or with a Continue / Break node
+            match rt.run_loop_body(id, "Loop") {
+                Some(Jump::Break) => break,
+                Some(Jump::Continue) => continue,
+                _ => {}
+            }
+
+            // Wait for Continue signal
+            rt.log(format!("ForLoopAsync: Waiting for Continue signal (iteration {})", i));
+            loop {
+                if rt.should_stop() {
+                    rt.log("ForLoopAsync: Stop requested during wait".to_string());
+                    break;
+                }
+
+                let signalled = rt.context.lock().unwrap().update_node_state(id, |state| {
+                    matches!(state, Some(VariableValue::Boolean(true)))
+                });
+                if signalled {
+                    rt.log(format!("ForLoopAsync: Continue signal received (iteration {})", i));
+                    break;
+                }
+
+                thread::sleep(Duration::from_millis(50));
+            }
+        }
+
+        // Continue to Done
+        rt.flow_to(id, "Done")
+    }
+}
+
+pub struct ForEach;
+
+impl NodeExecutor for ForEach {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let arr = match rt.input(id, "Array") {
+            Ok(VariableValue::Array(arr)) => arr,
+            _ => vec![],
+        };
+
+        rt.log(format!("ForEach: Processing {} elements", arr.len()));
+
+        for (i, element) in arr.into_iter().enumerate() {
+            if rt.loop_interrupted("ForEach") {
+                break;
+            }
+
+            set_element(rt, id, element, i);
+            if rt.run_loop_body(id, "Loop") == Some(Jump::Break) {
+                break;
+            }
+        }
+
+        // Continue to Done
+        rt.flow_to(id, "Done")
+    }
+}
+
+/// Publish the current element and index of an array loop
+fn set_element(rt: &FlowRuntime, id: Uuid, element: VariableValue, index: usize) {
+    let mut ctx = rt.context.lock().unwrap();
+    ctx.set_output(id, "Element", element);
+    ctx.set_output(id, "Index", VariableValue::Integer(index as i64));
+}
+
+pub struct ArrayFilter;
+
+impl NodeExecutor for ArrayFilter {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let arr = match rt.input(id, "Array") {
+            Ok(VariableValue::Array(arr)) => arr,
+            _ => vec![],
+        };
+        let total = arr.len();
+
+        // Body runs first, then Keep is read for the element; Break keeps
+        // what was decided so far
+        let mut result = Vec::new();
+        for (i, element) in arr.into_iter().enumerate() {
+            if rt.loop_interrupted("ArrayFilter") {
+                break;
+            }
+
+            set_element(rt, id, element.clone(), i);
+            if rt.run_loop_body(id, "Body") == Some(Jump::Break) {
+                break;
+            }
+            let keep = rt.input(id, "Keep").unwrap_or(VariableValue::Boolean(true));
+            if Interpreter::to_bool(&keep) {
+                result.push(element);
+            }
+        }
+
+        rt.log(format!("ArrayFilter: Kept {} of {} elements", result.len(), total));
+        rt.context
+            .lock()
+            .unwrap()
+            .set_output(id, "Result", VariableValue::Array(result));
+        rt.flow_to(id, "Done")
+    }
+}
+
+pub struct ArrayMap;
+
+impl NodeExecutor for ArrayMap {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let arr = match rt.input(id, "Array") {
+            Ok(VariableValue::Array(arr)) => arr,
+            _ => vec![],
+        };
+
+        rt.log(format!("ArrayMap: Processing {} elements", arr.len()));
+
+        // Body runs first, then Value is read as the new element
+        let mut result = Vec::new();
+        for (i, element) in arr.into_iter().enumerate() {
+            if rt.loop_interrupted("ArrayMap") {
+                break;
+            }
+
+            set_element(rt, id, element, i);
+            if rt.run_loop_body(id, "Body") == Some(Jump::Break) {
+                break;
+            }
+            result.push(rt.input(id, "Value").unwrap_or(VariableValue::None));
+        }
+
+        rt.context
+            .lock()
+            .unwrap()
+            .set_output(id, "Result", VariableValue::Array(result));
+        rt.flow_to(id, "Done")
+    }
+}
+
+pub struct ForEachLine;
+
+impl NodeExecutor for ForEachLine {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let text = rt
+            .input(id, "Text")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+
+        let lines: Vec<&str> = text.lines().collect();
+
+        rt.log(format!("ForEachLine: Processing {} lines", lines.len()));
+
+        for (i, line) in lines.iter().enumerate() {
+            if rt.loop_interrupted("ForEachLine") {
+                break;
+            }
+
+            // Set Line and Index outputs
+            {
+                let mut ctx = rt.context.lock().unwrap();
+                ctx.set_output(id, "Line", VariableValue::String(line.to_string()));
+                ctx.set_output(id, "Index", VariableValue::Integer(i as i64));
+            }
+
+            if rt.run_loop_body(id, "Loop") == Some(Jump::Break) {
+                break;
+            }
+        }
+
+        // Continue to Done
+        rt.flow_to(id, "Done")
+    }
+}
+
+pub struct TryCatch;
+
+impl NodeExecutor for TryCatch {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+
+        // Failures inside Try that are not handled by an Error port land here
+        let error = rt.run_try(id, "Try");
+
+        let port = match &error {
+            Some(message) => {
+                rt.log(format!("TryCatch: Caught error - {}", message));
+                "Catch"
+            }
+            None => "Completed",
+        };
+        rt.context.lock().unwrap().set_output(
+            id,
+            "ErrorMessage",
+            VariableValue::String(error.unwrap_or_default()),
+        );
+
+        rt.flow_to(id, port)
+    }
+}
+
+/// Break, Continue and Return: end the chain and unwind to the innermost
+/// loop, or to the start of the flow or function call
+pub struct Jumps(pub Jump);
+
+impl NodeExecutor for Jumps {
+    fn execute(&self, rt: &mut FlowRuntime, _node: &Node) -> Flow {
+        rt.jump(self.0)
+    }
+}
@@ -0,0 +1,76 @@
+//! User function and script calls.
+
+use super::{Flow, NodeExecutor};
+use crate::executor::runtime::FlowRuntime;
+use crate::graph::{Node, VariableValue};
+use crate::node_types::{DataType, NodeType};
+
+pub struct CallFunction;
+
+impl NodeExecutor for CallFunction {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let NodeType::CallFunction { name } = &node.node_type else {
+            return Flow::End;
+        };
+
+        // Parameter ports mirror the function's InputParam nodes
+        let args = node
+            .inputs
+            .iter()
+            .filter(|p| p.data_type != DataType::ExecutionFlow)
+            .map(|p| {
+                let value = rt.input(id, &p.name).unwrap_or(VariableValue::None);
+                (p.name.clone(), value)
+            })
+            .collect();
+
+        match rt.call_function(name, args) {
+            Ok(results) => {
+                let mut ctx = rt.context.lock().unwrap();
+                for (port, value) in results {
+                    ctx.set_output(id, &port, value);
+                }
+            }
+            Err(message) => return rt.fail(id, message),
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct CallScript;
+
+impl NodeExecutor for CallScript {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let NodeType::CallScript { name } = &node.node_type else {
+            return Flow::End;
+        };
+
+        // Argument ports mirror the script's variables
+        let args = node
+            .inputs
+            .iter()
+            .filter(|p| p.data_type != DataType::ExecutionFlow)
+            .map(|p| {
+                let value = rt.input(id, &p.name).unwrap_or(VariableValue::None);
+                (p.name.clone(), value)
+            })
+            .collect();
+
+        match rt.call_script(name, args) {
+            Ok(variables) => {
+                let mut ctx = rt.context.lock().unwrap();
+                for port in &node.outputs {
+                    if let Some(value) = variables.get(&port.name) {
+                        ctx.set_output(id, &port.name, value.clone());
+                    }
+                }
+            }
+            Err(message) => return rt.fail(id, message),
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
@@ -0,0 +1,879 @@
+//! Screen capture and image/color recognition nodes. Captures are handed on
+//! as in-memory images and also saved, so both kinds of input keep working.
+
+use super::{Flow, NodeExecutor};
+use crate::executor::{Interpreter, image_matching};
+use crate::executor::runtime::FlowRuntime;
+use crate::graph::{ImageValue, Node, VariableValue};
+use std::time::Duration;
+use uuid::Uuid;
+use xcap::Monitor;
+
+/// Capture the primary monitor, with its scale factor (physical / logical width)
+fn capture_primary() -> Result<(image::RgbaImage, f32), String> {
+    let monitors = Monitor::all().map_err(|e| format!("Monitor error - {}", e))?;
+    let monitor = monitors.first().ok_or("No monitors found")?;
+    let screen = monitor
+        .capture_image()
+        .map_err(|e| format!("Capture error - {}", e))?;
+    let logical_width = monitor.width().ok().unwrap_or(screen.width()) as f32;
+    let scale_factor = screen.width() as f32 / logical_width;
+    Ok((screen, scale_factor))
+}
+
+/// In-memory image wired into `port`, if any
+fn image_input(rt: &FlowRuntime, id: Uuid, port: &str) -> Option<ImageValue> {
+    match rt.input(id, port) {
+        Ok(VariableValue::Image(image)) => Some(image),
+        _ => None,
+    }
+}
+
+/// Template of FindImage / WaitForImage: the `Template` image if one is
+/// wired in, else the `ImagePath` input
+fn template_input(rt: &FlowRuntime, id: Uuid) -> VariableValue {
+    match image_input(rt, id, "Template") {
+        Some(image) => VariableValue::Image(image),
+        None => rt.input(id, "ImagePath").unwrap_or(VariableValue::None),
+    }
+}
+
+pub struct ScreenCapture;
+
+impl NodeExecutor for ScreenCapture {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let display_index =
+            rt.input(id, "Display")
+                .map(|v| match v {
+                    VariableValue::Integer(i) => i as usize,
+                    VariableValue::Float(f) => f as usize,
+                    _ => 0,
+                })
+                .unwrap_or(0);
+
+        logger(format!("ScreenCapture: Display {}", display_index));
+
+        // Ensure screenshots directory exists
+        let _ = std::fs::create_dir_all("scripts/screenshots");
+
+        // Capture screen using xcap
+        let mut error = None;
+        let mut captured = None;
+        let (success, image_path) = match Monitor::all() {
+            Ok(monitors) => {
+                if let Some(monitor) = monitors.get(display_index) {
+                    match monitor.capture_image() {
+                        Ok(image) => {
+                            let image = captured.insert(ImageValue::new(image));
+                            let timestamp =
+                                chrono::Local::now().format("%Y%m%d_%H%M%S_%3f");
+                            let filename = format!(
+                                "scripts/screenshots/capture_{}.png",
+                                timestamp
+                            );
+                            match image.0.save(&filename) {
+                                Ok(_) => {
+                                    logger(format!(
+                                        "ScreenCapture: Saved to {}",
+                                        filename
+                                    ));
+                                    (true, filename)
+                                }
+                                Err(e) => {
+                                    error = Some(format!(
+                                        "ScreenCapture: Save error - {}",
+                                        e
+                                    ));
+                                    (false, String::new())
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            error = Some(format!("ScreenCapture: Capture error - {}", e));
+                            (false, String::new())
+                        }
+                    }
+                } else {
+                    error = Some(format!(
+                        "ScreenCapture: Display {} not found, only {} displays available",
+                        display_index,
+                        monitors.len()
+                    ));
+                    (false, String::new())
+                }
+            }
+            Err(e) => {
+                error = Some(format!("ScreenCapture: Monitor error - {}", e));
+                (false, String::new())
+            }
+        };
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "Image", captured.map_or(VariableValue::None, VariableValue::Image));
+            ctx.set_output(id, "ImagePath", VariableValue::String(image_path));
+            ctx.set_output(id, "Success", VariableValue::Boolean(success));
+        }
+
+        if let Some(message) = error {
+            return rt.fail(id, message);
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct SaveScreenshot;
+
+impl NodeExecutor for SaveScreenshot {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        // An in-memory image is encoded to the file, a path is copied
+        let image = image_input(rt, id, "Image").or_else(|| image_input(rt, id, "ImagePath"));
+        let image_path =
+            rt.input(id, "ImagePath")
+                .map(|v| Interpreter::to_string(&v))
+                .unwrap_or_default();
+        let filename =
+            rt.input(id, "Filename")
+                .map(|v| Interpreter::to_string(&v))
+                .unwrap_or_else(|_| "screenshot.png".to_string());
+
+        let source = image.as_ref().map_or(image_path.clone(), |i| format!("{:?}", i));
+        logger(format!("SaveScreenshot: {} -> {}", source, filename));
+
+        // Ensure target directory exists
+        if let Some(parent) = std::path::Path::new(&filename).parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        let mut error = None;
+        let (success, saved_path) = if let Some(image) = image {
+            match image.0.save(&filename) {
+                Ok(_) => {
+                    logger(format!("SaveScreenshot: Saved to {}", filename));
+                    (true, filename.clone())
+                }
+                Err(e) => {
+                    error = Some(format!("SaveScreenshot: Save error - {}", e));
+                    (false, String::new())
+                }
+            }
+        } else if !image_path.is_empty() {
+            match std::fs::copy(&image_path, &filename) {
+                Ok(_) => {
+                    logger(format!("SaveScreenshot: Saved to {}", filename));
+                    (true, filename.clone())
+                }
+                Err(e) => {
+                    error = Some(format!("SaveScreenshot: Copy error - {}", e));
+                    (false, String::new())
+                }
+            }
+        } else {
+            error = Some("SaveScreenshot: No image or image path provided".to_string());
+            (false, String::new())
+        };
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "SavedPath", VariableValue::String(saved_path));
+            ctx.set_output(id, "Success", VariableValue::Boolean(success));
+        }
+
+        if let Some(message) = error {
+            return rt.fail(id, message);
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct RegionCapture;
+
+impl NodeExecutor for RegionCapture {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let x = rt.input(id, "X")
+            .map(|v| Interpreter::to_float(&v) as u32)
+            .unwrap_or(0);
+        let y = rt.input(id, "Y")
+            .map(|v| Interpreter::to_float(&v) as u32)
+            .unwrap_or(0);
+        let width = rt.input(id, "Width")
+            .map(|v| Interpreter::to_float(&v) as u32)
+            .unwrap_or(200);
+        let height = rt.input(id, "Height")
+            .map(|v| Interpreter::to_float(&v) as u32)
+            .unwrap_or(100);
+        let custom_filename =
+            rt.input(id, "Filename")
+                .map(|v| Interpreter::to_string(&v))
+                .unwrap_or_default();
+
+        logger(format!(
+            "RegionCapture: ({},{}) {}x{}",
+            x, y, width, height
+        ));
+
+        // Ensure templates directory exists
+        let _ = std::fs::create_dir_all("scripts/templates");
+
+        // Capture screen and crop to region
+        let mut error = None;
+        let mut captured = None;
+        let (success, image_path) = match Monitor::all() {
+            Ok(monitors) => {
+                if let Some(monitor) = monitors.first() {
+                    match monitor.capture_image() {
+                        Ok(full_image) => {
+                            // Validate bounds
+                            let img_width = full_image.width();
+                            let img_height = full_image.height();
+
+                            if x >= img_width || y >= img_height {
+                                error = Some(format!(
+                                    "RegionCapture: Start position ({},{}) out of bounds ({}x{})",
+                                    x, y, img_width, img_height
+                                ));
+                                (false, String::new())
+                            } else {
+                                // Clamp width/height to image bounds
+                                let crop_width = width.min(img_width - x);
+                                let crop_height = height.min(img_height - y);
+
+                                // Crop the image
+                                let cropped = captured.insert(ImageValue::new(
+                                    image::imageops::crop_imm(
+                                        &full_image, x, y, crop_width, crop_height
+                                    ).to_image(),
+                                ));
+
+                                // Generate filename
+                                let filename = if custom_filename.is_empty() {
+                                    let timestamp = chrono::Local::now()
+                                        .format("%Y%m%d_%H%M%S_%3f");
+                                    format!("scripts/templates/region_{}.png", timestamp)
+                                } else if custom_filename.contains('/') || custom_filename.contains('\\') {
+                                    // User provided full path
+                                    custom_filename.clone()
+                                } else {
+                                    // Just filename, put in templates folder
+                                    format!("scripts/templates/{}", custom_filename)
+                                };
+
+                                // Save cropped image
+                                match cropped.0.save(&filename) {
+                                    Ok(_) => {
+                                        logger(format!(
+                                            "RegionCapture: Saved {}x{} to {}",
+                                            crop_width, crop_height, filename
+                                        ));
+                                        (true, filename)
+                                    }
+                                    Err(e) => {
+                                        error = Some(format!(
+                                            "RegionCapture: Save error - {}",
+                                            e
+                                        ));
+                                        (false, String::new())
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            error = Some(format!("RegionCapture: Capture error - {}", e));
+                            (false, String::new())
+                        }
+                    }
+                } else {
+                    error = Some("RegionCapture: No monitors found".to_string());
+                    (false, String::new())
+                }
+            }
+            Err(e) => {
+                error = Some(format!("RegionCapture: Monitor error - {}", e));
+                (false, String::new())
+            }
+        };
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "Image", captured.map_or(VariableValue::None, VariableValue::Image));
+            ctx.set_output(id, "ImagePath", VariableValue::String(image_path));
+            ctx.set_output(id, "Success", VariableValue::Boolean(success));
+        }
+
+        if let Some(message) = error {
+            return rt.fail(id, message);
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct GetPixelColor;
+
+impl NodeExecutor for GetPixelColor {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let x = rt.input(id, "X")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as u32,
+                VariableValue::Float(f) => f as u32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+        let y = rt.input(id, "Y")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as u32,
+                VariableValue::Float(f) => f as u32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+
+        logger(format!("GetPixelColor: ({}, {})", x, y));
+
+        // A given image is read in its own pixels, the live screen is captured
+        // Note: x,y are in LOGICAL pixels, xcap captures in PHYSICAL pixels
+        let (r, g, b, success) = if let Some(image) = image_input(rt, id, "Image") {
+            if x < image.0.width() && y < image.0.height() {
+                let pixel = image.0.get_pixel(x, y);
+                (pixel[0] as i64, pixel[1] as i64, pixel[2] as i64, true)
+            } else {
+                logger(format!("GetPixelColor: Coordinates out of bounds of {:?}", image));
+                (0, 0, 0, false)
+            }
+        } else {
+            match xcap::Monitor::all() {
+                Ok(monitors) => {
+                    if let Some(monitor) = monitors.first() {
+                        match monitor.capture_image() {
+                            Ok(img) => {
+                                // Calculate DPI scale factor
+                                let logical_width = monitor.width().ok().unwrap_or(img.width()) as f32;
+                                let physical_width = img.width() as f32;
+                                let scale_factor = physical_width / logical_width;
+
+                                // Convert logical coords to physical pixels
+                                let physical_x = (x as f32 * scale_factor) as u32;
+                                let physical_y = (y as f32 * scale_factor) as u32;
+
+                                logger(format!("GetPixelColor: logical ({},{}) -> physical ({},{}) scale={:.1}",
+                                    x, y, physical_x, physical_y, scale_factor));
+
+                                if physical_x < img.width() && physical_y < img.height() {
+                                    let pixel = img.get_pixel(physical_x, physical_y);
+                                    (
+                                        pixel[0] as i64,
+                                        pixel[1] as i64,
+                                        pixel[2] as i64,
+                                        true,
+                                    )
+                                } else {
+                                    logger(format!(
+                                        "GetPixelColor: Coordinates out of bounds"
+                                    ));
+                                    (0, 0, 0, false)
+                                }
+                            }
+                            Err(e) => {
+                                logger(format!("GetPixelColor: Capture error - {}", e));
+                                (0, 0, 0, false)
+                            }
+                        }
+                    } else {
+                        logger("GetPixelColor: No monitors found".to_string());
+                        (0, 0, 0, false)
+                    }
+                }
+                Err(e) => {
+                    logger(format!("GetPixelColor: Monitor error - {}", e));
+                    (0, 0, 0, false)
+                }
+            }
+        };
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "R", VariableValue::Integer(r));
+            ctx.set_output(id, "G", VariableValue::Integer(g));
+            ctx.set_output(id, "B", VariableValue::Integer(b));
+            ctx.set_output(id, "Success", VariableValue::Boolean(success));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct FindColor;
+
+impl NodeExecutor for FindColor {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let target_r = rt.input(id, "R")
+            .map(|v| Interpreter::to_float(&v) as u8)
+            .unwrap_or(255);
+        let target_g = rt.input(id, "G")
+            .map(|v| Interpreter::to_float(&v) as u8)
+            .unwrap_or(0);
+        let target_b = rt.input(id, "B")
+            .map(|v| Interpreter::to_float(&v) as u8)
+            .unwrap_or(0);
+        let tolerance =
+            rt.input(id, "Tolerance")
+                .map(|v| Interpreter::to_float(&v) as i32)
+                .unwrap_or(10);
+        let region_x =
+            rt.input(id, "RegionX")
+                .map(|v| Interpreter::to_float(&v) as u32)
+                .unwrap_or(0);
+        let region_y =
+            rt.input(id, "RegionY")
+                .map(|v| Interpreter::to_float(&v) as u32)
+                .unwrap_or(0);
+        let region_w =
+            rt.input(id, "RegionW")
+                .map(|v| Interpreter::to_float(&v) as u32)
+                .unwrap_or(1920);
+        let region_h =
+            rt.input(id, "RegionH")
+                .map(|v| Interpreter::to_float(&v) as u32)
+                .unwrap_or(1080);
+
+        // A wired Region rect replaces RegionX/Y/W/H
+        let (region_x, region_y, region_w, region_h) =
+            match rt.input(id, "Region").ok().and_then(|v| Interpreter::to_rect(&v)) {
+                Some((x, y, w, h)) => (
+                    x.max(0) as u32,
+                    y.max(0) as u32,
+                    w.max(0) as u32,
+                    h.max(0) as u32,
+                ),
+                None => (region_x, region_y, region_w, region_h),
+            };
+
+        logger(format!(
+            "FindColor: RGB({},{},{}) tolerance={} in region ({},{})x{}x{}",
+            target_r,
+            target_g,
+            target_b,
+            tolerance,
+            region_x,
+            region_y,
+            region_w,
+            region_h
+        ));
+
+        // Note: region coords are in LOGICAL pixels, xcap captures in PHYSICAL pixels
+        let (found_x, found_y, found) = match xcap::Monitor::all() {
+            Ok(monitors) => {
+                if let Some(monitor) = monitors.first() {
+                    match monitor.capture_image() {
+                        Ok(img) => {
+                            // Calculate DPI scale factor
+                            let logical_width = monitor.width().ok().unwrap_or(img.width()) as f32;
+                            let physical_width = img.width() as f32;
+                            let scale_factor = physical_width / logical_width;
+
+                            // Convert logical region to physical pixels
+                            let phys_region_x = (region_x as f32 * scale_factor) as u32;
+                            let phys_region_y = (region_y as f32 * scale_factor) as u32;
+                            let phys_region_w = (region_w as f32 * scale_factor) as u32;
+                            let phys_region_h = (region_h as f32 * scale_factor) as u32;
+
+                            let mut result = (0i64, 0i64, false);
+                            let end_x = (phys_region_x + phys_region_w).min(img.width());
+                            let end_y = (phys_region_y + phys_region_h).min(img.height());
+
+                            'outer: for py in phys_region_y..end_y {
+                                for px in phys_region_x..end_x {
+                                    let pixel = img.get_pixel(px, py);
+                                    let dr = (pixel[0] as i32 - target_r as i32).abs();
+                                    let dg = (pixel[1] as i32 - target_g as i32).abs();
+                                    let db = (pixel[2] as i32 - target_b as i32).abs();
+                                    if dr <= tolerance
+                                        && dg <= tolerance
+                                        && db <= tolerance
+                                    {
+                                        // Convert found position back to logical pixels
+                                        let logical_x = (px as f32 / scale_factor) as i64;
+                                        let logical_y = (py as f32 / scale_factor) as i64;
+                                        result = (logical_x, logical_y, true);
+                                        break 'outer;
+                                    }
+                                }
+                            }
+                            result
+                        }
+                        Err(e) => {
+                            logger(format!("FindColor: Capture error - {}", e));
+                            (0, 0, false)
+                        }
+                    }
+                } else {
+                    (0, 0, false)
+                }
+            }
+            Err(e) => {
+                logger(format!("FindColor: Monitor error - {}", e));
+                (0, 0, false)
+            }
+        };
+
+        logger(format!(
+            "FindColor: Found={} at ({},{})",
+            found, found_x, found_y
+        ));
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "X", VariableValue::Integer(found_x));
+            ctx.set_output(id, "Y", VariableValue::Integer(found_y));
+            ctx.set_output(id, "Found", VariableValue::Boolean(found));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct WaitForColor;
+
+impl NodeExecutor for WaitForColor {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let target_r = rt.input(id, "R")
+            .map(|v| Interpreter::to_float(&v) as u8)
+            .unwrap_or(255);
+        let target_g = rt.input(id, "G")
+            .map(|v| Interpreter::to_float(&v) as u8)
+            .unwrap_or(0);
+        let target_b = rt.input(id, "B")
+            .map(|v| Interpreter::to_float(&v) as u8)
+            .unwrap_or(0);
+        let x = rt.input(id, "X")
+            .map(|v| Interpreter::to_float(&v) as u32)
+            .unwrap_or(0);
+        let y = rt.input(id, "Y")
+            .map(|v| Interpreter::to_float(&v) as u32)
+            .unwrap_or(0);
+        let tolerance =
+            rt.input(id, "Tolerance")
+                .map(|v| Interpreter::to_float(&v) as i32)
+                .unwrap_or(10);
+        let timeout_ms =
+            rt.input(id, "Timeout")
+                .map(|v| Interpreter::to_float(&v) as u64)
+                .unwrap_or(5000);
+
+        logger(format!(
+            "WaitForColor: RGB({},{},{}) at ({},{}) tolerance={} timeout={}ms",
+            target_r, target_g, target_b, x, y, tolerance, timeout_ms
+        ));
+
+        let start = std::time::Instant::now();
+        let mut found = false;
+
+        // Note: x,y are in LOGICAL pixels, xcap captures in PHYSICAL pixels
+        while start.elapsed().as_millis() < timeout_ms as u128 {
+            if rt.loop_interrupted("WaitForColor") {
+                break;
+            }
+            if let Ok(monitors) = xcap::Monitor::all() {
+                if let Some(monitor) = monitors.first() {
+                    if let Ok(img) = monitor.capture_image() {
+                        // Calculate DPI scale factor
+                        let logical_width = monitor.width().ok().unwrap_or(img.width()) as f32;
+                        let physical_width = img.width() as f32;
+                        let scale_factor = physical_width / logical_width;
+
+                        // Convert logical coords to physical pixels
+                        let physical_x = (x as f32 * scale_factor) as u32;
+                        let physical_y = (y as f32 * scale_factor) as u32;
+
+                        if physical_x < img.width() && physical_y < img.height() {
+                            let pixel = img.get_pixel(physical_x, physical_y);
+                            let dr = (pixel[0] as i32 - target_r as i32).abs();
+                            let dg = (pixel[1] as i32 - target_g as i32).abs();
+                            let db = (pixel[2] as i32 - target_b as i32).abs();
+                            if dr <= tolerance && dg <= tolerance && db <= tolerance {
+                                found = true;
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            rt.sleep(Duration::from_millis(100)); // Poll every 100ms
+        }
+
+        logger(format!("WaitForColor: Found={}", found));
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "Found", VariableValue::Boolean(found));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct FindImage;
+
+impl NodeExecutor for FindImage {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        // Template: an image wired into Template, else the file at ImagePath
+        // (decoded once per file change)
+        let template_input = template_input(rt, id);
+        let image_path = Interpreter::to_string(&template_input);
+        let screen_input = image_input(rt, id, "Screen");
+        let tolerance =
+            rt.input(id, "Tolerance")
+                .map(|v| Interpreter::to_float(&v) as i32)
+                .unwrap_or(10);
+        let region_x =
+            rt.input(id, "RegionX")
+                .map(|v| Interpreter::to_float(&v) as u32)
+                .unwrap_or(0);
+        let region_y =
+            rt.input(id, "RegionY")
+                .map(|v| Interpreter::to_float(&v) as u32)
+                .unwrap_or(0);
+        let region_w =
+            rt.input(id, "RegionW")
+                .map(|v| Interpreter::to_float(&v) as u32)
+                .unwrap_or(1920);
+        let region_h =
+            rt.input(id, "RegionH")
+                .map(|v| Interpreter::to_float(&v) as u32)
+                .unwrap_or(1080);
+        // A wired Region rect replaces RegionX/Y/W/H
+        let (region_x, region_y, region_w, region_h) =
+            match rt.input(id, "Region").ok().and_then(|v| Interpreter::to_rect(&v)) {
+                Some((x, y, w, h)) => (
+                    x.max(0) as u32,
+                    y.max(0) as u32,
+                    w.max(0) as u32,
+                    h.max(0) as u32,
+                ),
+                None => (region_x, region_y, region_w, region_h),
+            };
+        let algorithm_str =
+            rt.input(id, "Algorithm")
+                .map(|v| Interpreter::to_string(&v))
+                .unwrap_or_else(|_| "NCC".to_string());
+        let algorithm = image_matching::MatchingAlgorithm::from_str(&algorithm_str);
+
+        logger(format!(
+            "FindImage: {} tolerance={} algorithm={:?} in region ({},{})x{}x{}",
+            image_path, tolerance, algorithm, region_x, region_y, region_w, region_h
+        ));
+
+        if let VariableValue::String(path) = &template_input {
+            // Check if file exists and show absolute path for debugging
+            let abs_path = std::path::Path::new(path)
+                .canonicalize()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|_| format!("(not found: {})", path));
+            logger(format!("FindImage: Resolved path: {}", abs_path));
+        }
+
+        let mut error = None;
+        // Logical size of the template, for the Match rect
+        let mut match_size = (0i64, 0i64);
+        let (found_x, found_y, found) = match image_matching::image_from_value(&template_input) {
+            Ok(template) => {
+                let template = &template.0;
+                logger(format!(
+                    "FindImage: Template loaded {}x{} pixels",
+                    template.width(), template.height()
+                ));
+                // A given screen image is searched in its own pixels
+                let screen = match screen_input {
+                    Some(screen) => Ok((screen, 1.0)),
+                    None => capture_primary().map(|(screen, scale)| (ImageValue::new(screen), scale)),
+                };
+                match screen {
+                    Ok((screen, scale_factor)) => {
+                        let screen = &screen.0;
+                        match_size = (
+                            (template.width() as f32 / scale_factor) as i64,
+                            (template.height() as f32 / scale_factor) as i64,
+                        );
+                        logger(format!(
+                            "FindImage: Screen {}x{} physical, scale={:.1}x",
+                            screen.width(), screen.height(), scale_factor
+                        ));
+
+                        // Log effective search region (in physical pixels)
+                        let phys_w = (region_w as f32 * scale_factor) as u32;
+                        let phys_h = (region_h as f32 * scale_factor) as u32;
+                        logger(format!(
+                            "FindImage: Search region {}x{} (logical {}x{}), template {}x{}",
+                            phys_w, phys_h, region_w, region_h, template.width(), template.height()
+                        ));
+
+                        logger(format!("FindImage: Starting {:?} matching (parallel)...", algorithm));
+                        let start_time = std::time::Instant::now();
+                        let result = Interpreter::find_template_in_image(
+                            screen, template, tolerance, region_x, region_y,
+                            region_w, region_h, scale_factor, algorithm,
+                        );
+                        logger(format!(
+                            "FindImage: Template matching took {:.2}s",
+                            start_time.elapsed().as_secs_f64()
+                        ));
+                        result
+                    }
+                    Err(e) => {
+                        error = Some(format!("FindImage: {}", e));
+                        (0, 0, false)
+                    }
+                }
+            }
+            Err(e) => {
+                error = Some(format!("FindImage: Template load error - {:#} ({})", e, image_path));
+                (0, 0, false)
+            }
+        };
+
+        logger(format!(
+            "FindImage: Found={} at ({},{})",
+            found, found_x, found_y
+        ));
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "X", VariableValue::Integer(found_x));
+            ctx.set_output(id, "Y", VariableValue::Integer(found_y));
+            ctx.set_output(id, "Found", VariableValue::Boolean(found));
+            // X/Y is the center of the match
+            let (w, h) = if found { match_size } else { (0, 0) };
+            let (x, y) = if found { (found_x - w / 2, found_y - h / 2) } else { (0, 0) };
+            ctx.set_output(id, "Match", VariableValue::Rect(x, y, w, h));
+        }
+
+        if let Some(message) = error {
+            return rt.fail(id, message);
+        }
+
+        logger(format!("FindImage: Execution complete, looking for Next connection..."));
+
+        match rt.follow(id, "Next") {
+            Some(next) => {
+                logger(format!("FindImage: Following flow to next node"));
+                Flow::Next(next)
+            }
+            None => {
+                logger(format!("FindImage: No Next connection found, ending flow"));
+                Flow::End
+            }
+        }
+    }
+}
+
+pub struct WaitForImage;
+
+impl NodeExecutor for WaitForImage {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let template_input = template_input(rt, id);
+        let image_path = Interpreter::to_string(&template_input);
+        let tolerance =
+            rt.input(id, "Tolerance")
+                .map(|v| Interpreter::to_float(&v) as i32)
+                .unwrap_or(10);
+        let timeout_ms =
+            rt.input(id, "Timeout")
+                .map(|v| Interpreter::to_float(&v) as u64)
+                .unwrap_or(5000);
+
+        logger(format!(
+            "WaitForImage: {} tolerance={} timeout={}ms",
+            image_path, tolerance, timeout_ms
+        ));
+
+        let mut error = None;
+        let (found_x, found_y, found) = match image_matching::image_from_value(&template_input) {
+            Ok(template) => {
+                let template = &template.0;
+                let start = std::time::Instant::now();
+                let mut result = (0i64, 0i64, false);
+
+                while start.elapsed().as_millis() < timeout_ms as u128 {
+                    if rt.loop_interrupted("WaitForImage") {
+                        break;
+                    }
+                    if let Ok(monitors) = xcap::Monitor::all() {
+                        if let Some(monitor) = monitors.first() {
+                            if let Ok(screen) = monitor.capture_image() {
+                                // Detect scale factor
+                                let logical_width = monitor.width().ok().unwrap_or(screen.width()) as f32;
+                                let physical_width = screen.width() as f32;
+                                let scale_factor = physical_width / logical_width;
+
+                                let (fx, fy, f) = Interpreter::find_template_in_image(
+                                    &screen,
+                                    template,
+                                    tolerance,
+                                    0,
+                                    0,
+                                    screen.width(),
+                                    screen.height(),
+                                    scale_factor,
+                                    image_matching::MatchingAlgorithm::NCC, // WaitForImage uses NCC by default
+                                );
+                                if f {
+                                    result = (fx, fy, true);
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    rt.sleep(Duration::from_millis(200)); // Poll every 200ms
+                }
+                result
+            }
+            Err(e) => {
+                error = Some(format!("WaitForImage: Template load error - {:#}", e));
+                (0, 0, false)
+            }
+        };
+
+        logger(format!(
+            "WaitForImage: Found={} at ({},{})",
+            found, found_x, found_y
+        ));
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "X", VariableValue::Integer(found_x));
+            ctx.set_output(id, "Y", VariableValue::Integer(found_y));
+            ctx.set_output(id, "Found", VariableValue::Boolean(found));
+        }
+
+        if let Some(message) = error {
+            return rt.fail(id, message);
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
@@ -0,0 +1,557 @@
+//! Mouse and keyboard simulation nodes.
+
+use super::{Flow, NodeExecutor};
+use crate::executor::Interpreter;
+use crate::executor::runtime::FlowRuntime;
+use crate::graph::{Node, VariableValue};
+use enigo::{Button, Coordinate, Direction, Key, Keyboard, Mouse};
+use std::thread;
+use std::time::Duration;
+
+pub struct Click;
+
+impl NodeExecutor for Click {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let x = rt.input(id, "X")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as i32,
+                VariableValue::Float(f) => f as i32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+        let y = rt.input(id, "Y")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as i32,
+                VariableValue::Float(f) => f as i32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+
+        // A wired Point (or Rect, used at its center) replaces X and Y
+        let (x, y) = match rt.input(id, "Point").ok().and_then(|v| Interpreter::to_point(&v)) {
+            Some((px, py)) => (px as i32, py as i32),
+            None => (x, y),
+        };
+
+        logger(format!("Click: ({}, {})", x, y));
+
+        if let Some(enigo) = rt.enigo() {
+            let _ = enigo.move_mouse(x, y, Coordinate::Abs);
+            let _ = enigo.button(Button::Left, Direction::Click);
+        } else {
+            logger("Click Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct DoubleClick;
+
+impl NodeExecutor for DoubleClick {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let x = rt.input(id, "X")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as i32,
+                VariableValue::Float(f) => f as i32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+        let y = rt.input(id, "Y")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as i32,
+                VariableValue::Float(f) => f as i32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+
+        logger(format!("DoubleClick: ({}, {})", x, y));
+
+        if let Some(enigo) = rt.enigo() {
+            let _ = enigo.move_mouse(x, y, Coordinate::Abs);
+            let _ = enigo.button(Button::Left, Direction::Click);
+            let _ = enigo.button(Button::Left, Direction::Click);
+        } else {
+             logger("DoubleClick Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct RightClick;
+
+impl NodeExecutor for RightClick {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let x = rt.input(id, "X")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as i32,
+                VariableValue::Float(f) => f as i32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+        let y = rt.input(id, "Y")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as i32,
+                VariableValue::Float(f) => f as i32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+
+        logger(format!("RightClick: ({}, {})", x, y));
+
+        if let Some(enigo) = rt.enigo() {
+            let _ = enigo.move_mouse(x, y, Coordinate::Abs);
+            let _ = enigo.button(Button::Right, Direction::Click);
+        } else {
+            logger("RightClick Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct MouseMove;
+
+impl NodeExecutor for MouseMove {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let x = rt.input(id, "X")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as i32,
+                VariableValue::Float(f) => f as i32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+        let y = rt.input(id, "Y")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as i32,
+                VariableValue::Float(f) => f as i32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+
+        // A wired Point (or Rect, used at its center) replaces X and Y
+        let (x, y) = match rt.input(id, "Point").ok().and_then(|v| Interpreter::to_point(&v)) {
+            Some((px, py)) => (px as i32, py as i32),
+            None => (x, y),
+        };
+
+        logger(format!("MouseMove: ({}, {})", x, y));
+
+        if let Some(enigo) = rt.enigo() {
+            let _ = enigo.move_mouse(x, y, Coordinate::Abs);
+        } else {
+            logger("MouseMove Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct MouseDown;
+
+impl NodeExecutor for MouseDown {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let button_str =
+            rt.input(id, "Button")
+                .map(|v| Interpreter::to_string(&v))
+                .unwrap_or_else(|_| "left".to_string());
+
+        let button = match button_str.to_lowercase().as_str() {
+            "right" => Button::Right,
+            "middle" => Button::Middle,
+            _ => Button::Left,
+        };
+
+        let x_val = rt.input(id, "X").unwrap_or(VariableValue::Integer(0));
+        let y_val = rt.input(id, "Y").unwrap_or(VariableValue::Integer(0));
+
+        let x = match x_val {
+            VariableValue::Integer(i) => i as i32,
+            VariableValue::Float(f) => f as i32,
+            _ => 0,
+        };
+        let y = match y_val {
+            VariableValue::Integer(i) => i as i32,
+            VariableValue::Float(f) => f as i32,
+            _ => 0,
+        };
+
+        // Check if inputs are connected
+        let x_connected = rt.is_connected(id, "X");
+        let y_connected = rt.is_connected(id, "Y");
+
+        // Move only if inputs are explicitly provided (connected or non-zero)
+        let should_move = x_connected || y_connected || x != 0 || y != 0;
+
+        if should_move {
+            logger(format!("MouseDown: {} at ({}, {})", button_str, x, y));
+        } else {
+            logger(format!("MouseDown: {} (Current Position)", button_str));
+        }
+
+        if let Some(enigo) = rt.enigo() {
+            if should_move {
+                let _ = enigo.move_mouse(x, y, Coordinate::Abs);
+                // macOS requires a delay after move before press, otherwise events are ignored
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+            let _ = enigo.button(button, Direction::Press);
+        } else {
+            logger("MouseDown Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct MouseUp;
+
+impl NodeExecutor for MouseUp {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+         let button_str =
+            rt.input(id, "Button")
+                .map(|v| Interpreter::to_string(&v))
+                .unwrap_or_else(|_| "left".to_string());
+
+        let button = match button_str.to_lowercase().as_str() {
+            "right" => Button::Right,
+            "middle" => Button::Middle,
+            _ => Button::Left,
+        };
+
+        let x_val = rt.input(id, "X").unwrap_or(VariableValue::Integer(0));
+        let y_val = rt.input(id, "Y").unwrap_or(VariableValue::Integer(0));
+
+        let x = match x_val {
+            VariableValue::Integer(i) => i as i32,
+            VariableValue::Float(f) => f as i32,
+            _ => 0,
+        };
+        let y = match y_val {
+            VariableValue::Integer(i) => i as i32,
+            VariableValue::Float(f) => f as i32,
+            _ => 0,
+        };
+
+        // Check if inputs are connected
+        let x_connected = rt.is_connected(id, "X");
+        let y_connected = rt.is_connected(id, "Y");
+
+        // Move only if inputs are explicitly provided (connected or non-zero)
+        let should_move = x_connected || y_connected || x != 0 || y != 0;
+
+        if should_move {
+            logger(format!("MouseUp: {} at ({}, {})", button_str, x, y));
+        } else {
+            logger(format!("MouseUp: {} (Current Position)", button_str));
+        }
+
+        if let Some(enigo) = rt.enigo() {
+            if should_move {
+                let _ = enigo.move_mouse(x, y, Coordinate::Abs);
+                // macOS requires a delay after move before release
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+            let _ = enigo.button(button, Direction::Release);
+        } else {
+            logger("MouseUp Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct Scroll;
+
+impl NodeExecutor for Scroll {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let x = rt.input(id, "X")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as i32,
+                VariableValue::Float(f) => f as i32,
+                _ => 0,
+            })
+            .unwrap_or(0);
+        let y = rt.input(id, "Y")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as i32,
+                VariableValue::Float(f) => f as i32,
+                _ => 0,
+            })
+            .unwrap_or(-3);
+
+        logger(format!("Scroll: ({}, {})", x, y));
+
+        if let Some(enigo) = rt.enigo() {
+            let _ = enigo.scroll(x, enigo::Axis::Horizontal);
+            let _ = enigo.scroll(y, enigo::Axis::Vertical);
+        } else {
+            logger("Scroll Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct KeyPress;
+
+impl NodeExecutor for KeyPress {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let key_str = rt.input(id, "Key")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_else(|_| "Return".to_string());
+
+        logger(format!("KeyPress: {}", key_str));
+
+        if let Some(enigo) = rt.enigo() {
+            if let Some(key) = Interpreter::string_to_key(&key_str) {
+                let _ = enigo.key(key, Direction::Press);
+                thread::sleep(Duration::from_millis(50));
+                let _ = enigo.key(key, Direction::Release);
+            } else {
+                if let Some(c) = key_str.chars().next() {
+                    let _ = enigo.key(Key::Unicode(c), Direction::Click);
+                }
+            }
+        } else {
+            logger("KeyPress Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct KeyDown;
+
+impl NodeExecutor for KeyDown {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let key_str = rt.input(id, "Key")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_else(|_| "Shift".to_string());
+
+        logger(format!("KeyDown: {}", key_str));
+
+        if let Some(enigo) = rt.enigo() {
+            if let Some(key) = Interpreter::string_to_key(&key_str) {
+                let _ = enigo.key(key, Direction::Press);
+            } else {
+                if let Some(c) = key_str.chars().next() {
+                    let _ = enigo.key(Key::Unicode(c), Direction::Press);
+                }
+            }
+        } else {
+            logger("KeyDown Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct KeyUp;
+
+impl NodeExecutor for KeyUp {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let key_str = rt.input(id, "Key")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_else(|_| "Shift".to_string());
+
+        logger(format!("KeyUp: {}", key_str));
+
+        if let Some(enigo) = rt.enigo() {
+            if let Some(key) = Interpreter::string_to_key(&key_str) {
+                let _ = enigo.key(key, Direction::Release);
+            } else {
+                if let Some(c) = key_str.chars().next() {
+                    let _ = enigo.key(Key::Unicode(c), Direction::Release);
+                }
+            }
+        } else {
+            logger("KeyUp Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct TypeText;
+
+impl NodeExecutor for TypeText {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let text = rt.input(id, "Text")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+
+        logger(format!("TypeText: \"{}\"", text));
+
+        if let Some(enigo) = rt.enigo() {
+             let _ = enigo.text(&text);
+        } else {
+             logger("TypeText Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct TypeString;
+
+impl NodeExecutor for TypeString {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let text = rt.input(id, "Text")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        let delay_ms = rt.input(id, "Delay")
+            .map(|v| match v {
+                VariableValue::Integer(i) => i as u64,
+                VariableValue::Float(f) => f as u64,
+                _ => 50,
+            })
+            .unwrap_or(50);
+
+        logger(format!("TypeString: \"{}\" (delay: {}ms)", text, delay_ms));
+
+        if let Some(enigo) = rt.enigo() {
+            for c in text.chars() {
+                // Use enigo.text() for each character - more reliable than Key::Unicode on macOS
+                let char_str = c.to_string();
+                match enigo.text(&char_str) {
+                    Ok(_) => {}
+                    Err(e) => {
+                        logger(format!("TypeString: Error typing '{}': {:?}", c, e));
+                    }
+                }
+
+                if delay_ms > 0 {
+                    thread::sleep(Duration::from_millis(delay_ms));
+                }
+            }
+            logger(format!("TypeString: Completed typing {} characters", text.len()));
+        } else {
+            logger("TypeString Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct HotKey;
+
+impl NodeExecutor for HotKey {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let key_str = rt.input(id, "Key")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_else(|_| "c".to_string());
+        let ctrl = rt.input(id, "Ctrl")
+            .map(|v| Interpreter::to_bool(&v))
+            .unwrap_or(false);
+        let shift = rt.input(id, "Shift")
+            .map(|v| Interpreter::to_bool(&v))
+            .unwrap_or(false);
+        let alt = rt.input(id, "Alt")
+            .map(|v| Interpreter::to_bool(&v))
+            .unwrap_or(false);
+        let meta = rt.input(id, "Command")
+            .map(|v| Interpreter::to_bool(&v))
+            .unwrap_or(false);
+
+        let mut modifiers = Vec::new();
+        if ctrl {
+            modifiers.push("Ctrl");
+        }
+        if shift {
+            modifiers.push("Shift");
+        }
+        if alt {
+            modifiers.push("Alt");
+        }
+        if meta {
+            modifiers.push("Command");
+        }
+        logger(format!("HotKey: {}+{}", modifiers.join("+"), key_str));
+
+        // Refactored HotKey to reuse enigo instance
+        if let Some(enigo) = rt.enigo() {
+            // Press modifiers
+            if ctrl {
+                let _ = enigo.key(Key::Control, Direction::Press);
+            }
+            if shift {
+                let _ = enigo.key(Key::Shift, Direction::Press);
+            }
+            if alt {
+                let _ = enigo.key(Key::Alt, Direction::Press);
+            }
+            if meta {
+                let _ = enigo.key(Key::Meta, Direction::Press);
+            }
+
+            thread::sleep(Duration::from_millis(100));
+
+            if let Some(key) = Interpreter::string_to_key(&key_str) {
+                let _ = enigo.key(key, Direction::Click);
+            } else if let Some(c) = key_str.chars().next() {
+                let _ = enigo.key(Key::Unicode(c), Direction::Click);
+            }
+
+            thread::sleep(Duration::from_millis(50));
+
+            // Release modifiers (in reverse order)
+            if meta {
+                let _ = enigo.key(Key::Meta, Direction::Release);
+            }
+            if alt {
+                let _ = enigo.key(Key::Alt, Direction::Release);
+            }
+            if shift {
+                let _ = enigo.key(Key::Shift, Direction::Release);
+            }
+            if ctrl {
+                let _ = enigo.key(Key::Control, Direction::Release);
+            }
+        } else {
+            logger("HotKey Error: Enigo not initialized".into());
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
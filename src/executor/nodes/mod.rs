@@ -0,0 +1,146 @@
+//! # Node Executors
+//!
+//! The single registry mapping exec-flow node types to their behavior.
+//!
+//! Every flow node implements [`NodeExecutor`]; [`executor_for`] is the only
+//! dispatch table, used for top-level flows and nested bodies alike. Pure
+//! data nodes are not listed here; they are evaluated on demand by
+//! `Interpreter::evaluate_node`.
+//!
+//! ## Adding a node
+//! 1. Implement [`NodeExecutor`] in the matching category module
+//! 2. Add it to [`executor_for`]
+
+pub mod data;
+pub mod flow;
+pub mod function;
+pub mod image;
+pub mod input;
+pub mod system;
+
+use super::runtime::{FlowRuntime, Jump};
+use crate::graph::Node;
+use crate::node_types::NodeType;
+use std::fmt;
+use uuid::Uuid;
+
+/// Where execution goes after a node has run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Flow {
+    /// Continue with this node
+    Next(Uuid),
+    /// This chain is finished
+    End,
+}
+
+impl From<Option<Uuid>> for Flow {
+    fn from(next: Option<Uuid>) -> Self {
+        next.map_or(Flow::End, Flow::Next)
+    }
+}
+
+/// A node failed at runtime.
+///
+/// Returned from `evaluate_node` by data nodes (e.g. JSONParse on bad input);
+/// the exec node reading the value then fails through [`FlowRuntime::fail`].
+#[derive(Debug, Clone)]
+pub struct NodeError(pub String);
+
+impl fmt::Display for NodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for NodeError {}
+
+/// Behavior of a node that sits on the execution flow.
+pub trait NodeExecutor: Sync {
+    /// Run the node and return the next step.
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow;
+}
+
+/// Look up the executor for a node type. `None` means the node has no
+/// exec behavior (pure data nodes, comments, events).
+pub fn executor_for(node_type: &NodeType) -> Option<&'static dyn NodeExecutor> {
+    let executor: &'static dyn NodeExecutor = match node_type {
+        // Data
+        NodeType::SetVariable { .. } => &data::SetVariable,
+        NodeType::BlueprintFunction { name } if name == "Print String" => &data::PrintString,
+        NodeType::FileWrite => &data::FileWrite,
+        NodeType::ArrayPush => &data::ArrayPush,
+        NodeType::ArrayPop => &data::ArrayPop,
+        NodeType::ArraySet => &data::ArraySet,
+        NodeType::ArrayRemoveAt => &data::ArrayRemoveAt,
+        NodeType::MapSet => &data::MapSet,
+        NodeType::MapRemove => &data::MapRemove,
+        NodeType::HTTPRequest => &data::HTTPRequest,
+
+        // Flow control
+        NodeType::Branch => &flow::Branch,
+        NodeType::Delay => &flow::Delay,
+        NodeType::ForLoop => &flow::ForLoop,
+        NodeType::WhileLoop => &flow::WhileLoop,
+        NodeType::Sequence => &flow::Sequence,
+        NodeType::Parallel => &flow::Parallel,
+        NodeType::Join => &flow::Join,
+        NodeType::Gate => &flow::Gate,
+        NodeType::WaitForCondition => &flow::WaitForCondition,
+        NodeType::ForLoopAsync => &flow::ForLoopAsync,
+        NodeType::ForEach => &flow::ForEach,
+        NodeType::ForEachLine => &flow::ForEachLine,
+        NodeType::ArrayFilter => &flow::ArrayFilter,
+        NodeType::ArrayMap => &flow::ArrayMap,
+        NodeType::TryCatch => &flow::TryCatch,
+        NodeType::SwitchOnString => &flow::SwitchOnString,
+        NodeType::SwitchOnInt => &flow::SwitchOnInt,
+        NodeType::DoOnce => &flow::DoOnce,
+        NodeType::DoN => &flow::DoN,
+        NodeType::FlipFlop => &flow::FlipFlop,
+        NodeType::Retry => &flow::Retry,
+        NodeType::WithTimeout => &flow::WithTimeout,
+        NodeType::Break => &flow::Jumps(Jump::Break),
+        NodeType::Continue => &flow::Jumps(Jump::Continue),
+        NodeType::Return => &flow::Jumps(Jump::Return),
+
+        // User functions
+        NodeType::CallFunction { .. } => &function::CallFunction,
+        NodeType::CallScript { .. } => &function::CallScript,
+
+        // Input simulation
+        NodeType::Click => &input::Click,
+        NodeType::DoubleClick => &input::DoubleClick,
+        NodeType::RightClick => &input::RightClick,
+        NodeType::MouseMove => &input::MouseMove,
+        NodeType::MouseDown => &input::MouseDown,
+        NodeType::MouseUp => &input::MouseUp,
+        NodeType::Scroll => &input::Scroll,
+        NodeType::KeyPress => &input::KeyPress,
+        NodeType::KeyDown => &input::KeyDown,
+        NodeType::KeyUp => &input::KeyUp,
+        NodeType::TypeText => &input::TypeText,
+        NodeType::TypeString => &input::TypeString,
+        NodeType::HotKey => &input::HotKey,
+
+        // System
+        NodeType::RunCommand => &system::RunCommand,
+        NodeType::LaunchApp => &system::LaunchApp,
+        NodeType::CloseApp => &system::CloseApp,
+        NodeType::GetWindowPosition => &system::GetWindowPosition,
+        NodeType::FocusWindow => &system::FocusWindow,
+        NodeType::SetWindowPosition => &system::SetWindowPosition,
+
+        // Screenshot & image recognition
+        NodeType::ScreenCapture => &image::ScreenCapture,
+        NodeType::SaveScreenshot => &image::SaveScreenshot,
+        NodeType::RegionCapture => &image::RegionCapture,
+        NodeType::GetPixelColor => &image::GetPixelColor,
+        NodeType::FindColor => &image::FindColor,
+        NodeType::WaitForColor => &image::WaitForColor,
+        NodeType::FindImage => &image::FindImage,
+        NodeType::WaitForImage => &image::WaitForImage,
+
+        _ => return None,
+    };
+    Some(executor)
+}
@@ -0,0 +1,357 @@
+//! System nodes: shell commands, applications and window management.
+
+use super::{Flow, NodeExecutor};
+use crate::executor::Interpreter;
+use crate::executor::runtime::FlowRuntime;
+use crate::graph::{Node, VariableValue};
+
+pub struct RunCommand;
+
+impl NodeExecutor for RunCommand {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let cmd = rt.input(id, "Command")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        let args_str = rt.input(id, "Args")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+
+        logger(format!("RunCommand: {} {}", cmd, args_str));
+
+        let args: Vec<&str> = args_str.split_whitespace().collect();
+
+        let mut error = None;
+        match std::process::Command::new(&cmd).args(&args).output() {
+            Ok(output) => {
+                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+                // let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+                let exit_code = output.status.code().unwrap_or(0) as i64;
+                let success = output.status.success();
+
+                let mut ctx = rt.context.lock().unwrap();
+                ctx.set_output(id, "Output", VariableValue::String(stdout));
+                ctx.set_output(id, "ExitCode", VariableValue::Integer(exit_code));
+                ctx.set_output(id, "Success", VariableValue::Boolean(success));
+            }
+            Err(e) => {
+                let mut ctx = rt.context.lock().unwrap();
+                ctx.set_output(id, "Success", VariableValue::Boolean(false));
+                error = Some(format!("RunCommand Error: {}", e));
+            }
+        }
+
+        if let Some(message) = error {
+            return rt.fail(id, message);
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct LaunchApp;
+
+impl NodeExecutor for LaunchApp {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let path = rt.input(id, "Path")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        let args_str = rt.input(id, "Args")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+
+        logger(format!("LaunchApp: {}", path));
+
+        let args: Vec<&str> = args_str.split_whitespace().collect();
+
+        #[cfg(target_os = "macos")]
+        let result = std::process::Command::new("open")
+            .arg(&path)
+            .args(&args)
+            .spawn();
+        #[cfg(target_os = "windows")]
+        let result = std::process::Command::new("cmd")
+            .arg("/C")
+            .arg("start")
+            .arg(&path)
+            .args(&args)
+            .spawn();
+        #[cfg(target_os = "linux")]
+        let result = std::process::Command::new("xdg-open")
+            .arg(&path)
+            .args(&args)
+            .spawn();
+        #[cfg(not(any(
+            target_os = "macos",
+            target_os = "windows",
+            target_os = "linux"
+        )))]
+        let result = std::process::Command::new(&path).args(&args).spawn();
+
+        let success = result.is_ok();
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "Success", VariableValue::Boolean(success));
+        }
+
+        if let Err(e) = result {
+            return rt.fail(id, format!("LaunchApp Error: {}", e));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct CloseApp;
+
+impl NodeExecutor for CloseApp {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let name = rt.input(id, "Name")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+
+        logger(format!("CloseApp: {}", name));
+
+        #[cfg(target_os = "windows")]
+        let result = std::process::Command::new("taskkill")
+            .args(["/IM", &name, "/F"])
+            .output();
+        #[cfg(not(target_os = "windows"))]
+        let result = std::process::Command::new("pkill")
+            .arg("-x")
+            .arg(&name)
+            .output();
+
+        // No matching process is not an error, only Success = false
+        let success = matches!(&result, Ok(output) if output.status.success());
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "Success", VariableValue::Boolean(success));
+        }
+
+        if let Err(e) = result {
+            return rt.fail(id, format!("CloseApp Error: {}", e));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct GetWindowPosition;
+
+impl NodeExecutor for GetWindowPosition {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        // Trigger evaluation to cache results
+        let _ = Interpreter::evaluate_node(&rt.graph, node, "X", &rt.context);
+
+        logger("GetWindowPosition: Executed".to_string());
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct FocusWindow;
+
+impl NodeExecutor for FocusWindow {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let title = rt.input(id, "Title")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+
+        logger(format!("FocusWindow: {}", title));
+
+        // Platform-specific implementation
+        #[cfg(target_os = "macos")]
+        let success = {
+            // Use AppleScript to find and focus window by title
+            let escaped_title = title.replace("\"", "\\\"");
+            let script = format!(
+                r#"tell application "System Events"
+                    set foundWindow to false
+                    repeat with proc in (every process whose visible is true)
+                        try
+                            repeat with w in windows of proc
+                                if name of w contains "{}" then
+                                    set frontmost of proc to true
+                                    perform action "AXRaise" of w
+                                    set foundWindow to true
+                                    exit repeat
+                                end if
+                            end repeat
+                            if foundWindow then exit repeat
+                        end try
+                    end repeat
+                    return foundWindow
+                end tell"#,
+                escaped_title
+            );
+            match std::process::Command::new("osascript")
+                .arg("-e")
+                .arg(&script)
+                .output()
+            {
+                Ok(output) => {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    output.status.success() && stdout.trim() == "true"
+                }
+                Err(e) => {
+                    logger(format!("FocusWindow Error: {}", e));
+                    false
+                }
+            }
+        };
+
+        #[cfg(target_os = "windows")]
+        let success = {
+            // Windows: Use PowerShell to find and focus window
+            logger(format!("FocusWindow (Windows stub): {}", title));
+            true // Stub for Windows
+        };
+
+        #[cfg(target_os = "linux")]
+        let success = {
+            // Linux: Use wmctrl or xdotool
+            let result = std::process::Command::new("wmctrl")
+                .args(["-a", &title])
+                .output();
+            result.map(|o| o.status.success()).unwrap_or(false)
+        };
+
+        #[cfg(not(any(
+            target_os = "macos",
+            target_os = "windows",
+            target_os = "linux"
+        )))]
+        let success = false;
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "Success", VariableValue::Boolean(success));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
+
+pub struct SetWindowPosition;
+
+impl NodeExecutor for SetWindowPosition {
+    fn execute(&self, rt: &mut FlowRuntime, node: &Node) -> Flow {
+        let id = node.id;
+        let logger = rt.logger();
+
+        let title = rt.input(id, "Title")
+            .map(|v| Interpreter::to_string(&v))
+            .unwrap_or_default();
+        let x = rt.input(id, "X")
+            .map(|v| Interpreter::to_float(&v) as i32)
+            .unwrap_or(0);
+        let y = rt.input(id, "Y")
+            .map(|v| Interpreter::to_float(&v) as i32)
+            .unwrap_or(0);
+        let w = rt.input(id, "Width")
+            .map(|v| Interpreter::to_float(&v) as i32)
+            .unwrap_or(800);
+        let h = rt.input(id, "Height")
+            .map(|v| Interpreter::to_float(&v) as i32)
+            .unwrap_or(600);
+
+        logger(format!(
+            "SetWindowPosition: '{}' -> {},{}, {}x{}",
+            title, x, y, w, h
+        ));
+
+        // Platform-specific implementation
+        #[cfg(target_os = "macos")]
+        let success = {
+            let escaped_title = title.replace("\"", "\\\"");
+            // AppleScript to set window position and size
+            let script = format!(
+                r#"tell application "System Events"
+                    set foundWindow to false
+                    repeat with proc in (every process whose visible is true)
+                        try
+                            repeat with w in windows of proc
+                                if name of w contains "{title}" then
+                                    set position of w to {{{x}, {y}}}
+                                    set size of w to {{{w}, {h}}}
+                                    set foundWindow to true
+                                    exit repeat
+                                end if
+                            end repeat
+                            if foundWindow then exit repeat
+                        end try
+                    end repeat
+                    return foundWindow
+                end tell"#,
+                title = escaped_title,
+                x = x,
+                y = y,
+                w = w,
+                h = h
+            );
+            match std::process::Command::new("osascript")
+                .arg("-e")
+                .arg(&script)
+                .output()
+            {
+                Ok(output) => {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    output.status.success() && stdout.trim() == "true"
+                }
+                Err(e) => {
+                    logger(format!("SetWindowPosition Error: {}", e));
+                    false
+                }
+            }
+        };
+
+        #[cfg(target_os = "windows")]
+        let success = {
+            logger(format!(
+                "SetWindowPosition (Windows stub): {} -> {},{},{}x{}",
+                title, x, y, w, h
+            ));
+            true // Stub for Windows
+        };
+
+        #[cfg(target_os = "linux")]
+        let success = {
+            // Linux: Use wmctrl to move window
+            let result = std::process::Command::new("wmctrl")
+                .args(["-r", &title, "-e", &format!("0,{},{},{},{}", x, y, w, h)])
+                .output();
+            result.map(|o| o.status.success()).unwrap_or(false)
+        };
+
+        #[cfg(not(any(
+            target_os = "macos",
+            target_os = "windows",
+            target_os = "linux"
+        )))]
+        let success = false;
+
+        {
+            let mut ctx = rt.context.lock().unwrap();
+            ctx.set_output(id, "Success", VariableValue::Boolean(success));
+        }
+
+        rt.flow_to(id, "Next")
+    }
+}
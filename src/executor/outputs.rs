@@ -0,0 +1,49 @@
+//! # Node Outputs
+//!
+//! Values produced by exec nodes (RunCommand output, FindImage position,
+//! HTTPRequest response, loop indices...), kept apart from user variables.
+//!
+//! The store lives in [`ExecutionContext`](super::ExecutionContext), so it is
+//! created empty when a run starts and dropped with the run. Lookups by
+//! `(node, port)` do not allocate.
+
+use crate::graph::VariableValue;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Output port values of every node that has executed in the current run.
+#[derive(Debug, Clone, Default)]
+pub struct NodeOutputs {
+    values: HashMap<Uuid, HashMap<String, VariableValue>>,
+}
+
+impl NodeOutputs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Value last written to `port` of `node_id`
+    pub fn get(&self, node_id: Uuid, port: &str) -> Option<&VariableValue> {
+        self.values.get(&node_id)?.get(port)
+    }
+
+    /// Store the value of an output port, replacing the previous one
+    pub fn set(&mut self, node_id: Uuid, port: &str, value: VariableValue) {
+        let ports = self.values.entry(node_id).or_default();
+        match ports.get_mut(port) {
+            Some(slot) => *slot = value,
+            None => {
+                ports.insert(port.to_string(), value);
+            }
+        }
+    }
+
+    /// All stored outputs as `(node, port, value)`
+    pub fn iter(&self) -> impl Iterator<Item = (Uuid, &str, &VariableValue)> {
+        self.values.iter().flat_map(|(id, ports)| {
+            ports
+                .iter()
+                .map(move |(port, value)| (*id, port.as_str(), value))
+        })
+    }
+}
@@ -0,0 +1,80 @@
+//! # Persistent Variables
+//!
+//! Variables marked [`persistent`](crate::graph::Variable::persistent) keep
+//! their final value between runs. The values are stored in a sidecar file
+//! next to the script (`scripts/foo.json` → `scripts/foo.state.json`), read
+//! when a run starts and written when it ends, including after Stop.
+
+use super::ExecutionContext;
+use crate::graph::{BlueprintGraph, VariableValue};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Sidecar state file of the script at `script`
+pub fn state_path(script: &Path) -> PathBuf {
+    script.with_extension("state.json")
+}
+
+/// Stored values by variable name; empty if the file does not exist yet
+pub fn load(path: &Path) -> Result<HashMap<String, VariableValue>> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&json).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+pub fn save(path: &Path, values: &HashMap<String, VariableValue>) -> Result<()> {
+    let json = serde_json::to_string_pretty(values)?;
+    std::fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Forget the stored value of `name` (`None`: of every variable), so the
+/// next run starts from the initial value again
+pub fn reset(path: &Path, name: Option<&str>) -> Result<()> {
+    let mut values = load(path)?;
+    match name {
+        Some(name) => {
+            values.remove(name);
+        }
+        None => values.clear(),
+    }
+    if values.is_empty() && path.exists() {
+        return std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove {}", path.display()));
+    }
+    save(path, &values)
+}
+
+/// Store the current values of the graph's persistent variables.
+///
+/// Values of variables no longer declared persistent are dropped from the
+/// file, and so are values holding an image (also inside an array or map).
+/// Does nothing for a graph without persistent variables.
+pub fn store_run(graph: &BlueprintGraph, context: &Mutex<ExecutionContext>, path: &Path) -> Result<()> {
+    if !graph.variables.values().any(|v| v.persistent) {
+        return Ok(());
+    }
+    let ctx = context.lock().unwrap();
+    let values = graph
+        .variables
+        .values()
+        .filter(|v| v.persistent)
+        .filter_map(|v| Some((v.name.clone(), ctx.variables.get(&v.name)?.clone())))
+        // Images only live for a run
+        .filter(|(_, value)| !contains_image(value))
+        .collect();
+    save(path, &values)
+}
+
+fn contains_image(value: &VariableValue) -> bool {
+    match value {
+        VariableValue::Image(_) => true,
+        VariableValue::Array(items) => items.iter().any(contains_image),
+        VariableValue::Map(map) => map.values().any(contains_image),
+        _ => false,
+    }
+}
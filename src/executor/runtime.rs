@@ -0,0 +1,806 @@
+//! # Flow Runtime
+//!
+//! Per-thread state for walking execution flow through a graph.
+//!
+//! A [`FlowRuntime`] is created for every flow (each firing of an event node,
+//! see [`triggers`](super::triggers)) and drives both the top-level chain and
+//! every nested body (loop bodies, Sequence outputs). Nodes are executed
+//! through the registry in [`nodes`](super::nodes), so a node behaves the
+//! same wherever it sits.
+//!
+//! The runtime also enforces the graph's [`ExecutionSettings`] (step budget,
+//! wall-clock limit and the action taken when one is reached). Both limits
+//! apply to the whole run: every flow, call and Parallel branch counts its
+//! steps against the run's [`RunBudget`].
+//!
+//! ## Node failures
+//! A node reports a failure with [`FlowRuntime::fail`]. The failure follows
+//! the node's `Error` port if connected, otherwise unwinds to the innermost
+//! TryCatch. Unhandled failures are logged and the flow continues on `Next`.
+//!
+//! ## Break, Continue and Return
+//! [`FlowRuntime::jump`] ends the current chain and makes every enclosing
+//! body unwind, like a failure does. Loops run their body through
+//! [`FlowRuntime::run_loop_body`], which stops the unwinding of a Break or
+//! Continue at the innermost loop. A Return unwinds to the start of the flow
+//! or function call.
+//!
+//! ## Timeouts
+//! [`FlowRuntime::run_body_with_timeout`] gives a body its own stop flag,
+//! raised by a watcher thread when the time is up or the run is stopped.
+//! Nodes inside the body (and the calls and branches they start) see it as a
+//! regular stop and end at their next check.
+//!
+//! ## Function calls
+//! [`FlowRuntime::call_function`] runs a user function body in a nested
+//! runtime with its own call frame (see [`ExecutionContext::call_frame`]).
+//! The callee shares the caller's step budget. A failure in the body that no
+//! Error port or TryCatch inside the body handles ends the call and fails the
+//! CallFunction node, which handles it like any other node failure.
+//!
+//! ## Script calls
+//! [`FlowRuntime::call_script`] loads another saved script and runs its
+//! OnStart flows on the calling thread, with a context of its own that shares
+//! the caller's stop flag and debugger. A script already running further up
+//! the chain of calls is rejected.
+//!
+//! ## Parallel branches
+//! A Parallel node runs each branch in its own runtime on its own thread,
+//! sharing the context and stop flag. The branches report to a [`ForkScope`]
+//! when they reach a Join or end; the Parallel node's runtime waits on it,
+//! joins every branch thread (cancelled branches end at their next node) and
+//! continues after the Join. Inside a TryCatch, a failure that ends a branch
+//! cancels the others and fails the Parallel node.
+
+use super::debug::{DebugControl, DepthGuard};
+use super::events::ExecutionEvent;
+use super::nodes::{self, Flow, NodeError};
+use super::triggers::Triggers;
+use super::{ExecutionContext, Interpreter};
+use super::view::ExecutionGraph;
+use crate::graph::{BlueprintGraph, ExecutionSettings, LimitAction, VariableValue};
+use crate::node_types::NodeType;
+use enigo::{Enigo, Settings};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+pub struct FlowRuntime {
+    pub graph: Arc<ExecutionGraph>,
+    pub context: Arc<Mutex<ExecutionContext>>,
+    pub tx: Sender<ExecutionEvent>,
+    stop_flag: Arc<AtomicBool>,
+    /// The run's stop flag, kept while `stop_flag` is a WithTimeout body's
+    run_stop: Arc<AtomicBool>,
+    debug: Arc<DebugControl>,
+    /// Input simulator, created on first use and reused for the whole flow
+    enigo: Option<Enigo>,
+    enigo_initialized: bool,
+    settings: ExecutionSettings,
+    /// Steps and start time of the run this flow belongs to
+    budget: Arc<RunBudget>,
+    /// This flow was ended by the watchdog
+    halted: bool,
+    /// Number of TryCatch bodies currently running
+    catch_depth: usize,
+    /// Failure unwinding to the innermost TryCatch
+    pending_error: Option<String>,
+    /// Failure of a data node read by the node currently executing
+    input_error: RefCell<Option<String>>,
+    /// Set when this flow is a branch of a Parallel node
+    fork: Option<Arc<ForkScope>>,
+    /// Number of user function calls this flow is nested in
+    call_depth: usize,
+    /// Number of loop bodies currently running
+    loop_depth: usize,
+    /// Break / Continue / Return unwinding the current bodies
+    jump: Option<Jump>,
+}
+
+/// Early exit requested by a Break, Continue or Return node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jump {
+    Break,
+    Continue,
+    Return,
+}
+
+impl FlowRuntime {
+    pub fn new(
+        graph: Arc<ExecutionGraph>,
+        context: Arc<Mutex<ExecutionContext>>,
+        tx: Sender<ExecutionEvent>,
+    ) -> Self {
+        let (stop_flag, debug, budget) = {
+            let ctx = context.lock().unwrap();
+            (ctx.stop_requested.clone(), ctx.debug.clone(), ctx.budget.clone())
+        };
+        let settings = graph.settings.clone();
+        Self {
+            graph,
+            context,
+            tx,
+            run_stop: stop_flag.clone(),
+            stop_flag,
+            debug,
+            enigo: None,
+            enigo_initialized: false,
+            settings,
+            budget,
+            halted: false,
+            catch_depth: 0,
+            pending_error: None,
+            input_error: RefCell::new(None),
+            fork: None,
+            call_depth: 0,
+            loop_depth: 0,
+            jump: None,
+        }
+    }
+
+    /// Make this flow a branch reporting to `scope`
+    pub fn in_fork(mut self, scope: Arc<ForkScope>) -> Self {
+        self.fork = Some(scope);
+        self
+    }
+
+    /// Runtime for one branch of a Parallel node, reporting to `scope`
+    pub fn branch(&self, scope: Arc<ForkScope>) -> Self {
+        let mut branch = FlowRuntime::new(self.graph.clone(), self.context.clone(), self.tx.clone())
+            .in_fork(scope)
+            .with_stop_flag(self.stop_flag.clone());
+        branch.run_stop = self.run_stop.clone();
+        branch.settings = self.settings.clone();
+        branch.call_depth = self.call_depth;
+        branch.catch_depth = self.catch_depth;
+        branch
+    }
+
+    /// Run this branch from `start`, then report to its scope. Returns the
+    /// failure that ended it inside a TryCatch; the other branches are
+    /// cancelled then.
+    pub fn run_branch(mut self, start: Uuid) -> Option<String> {
+        self.run_chain(start);
+        let error = self.pending_error.take();
+        if let Some(scope) = &self.fork {
+            if error.is_some() {
+                scope.cancel();
+            }
+            scope.finish();
+        }
+        error
+    }
+
+    /// Use `flag` instead of the run's stop flag (inside a WithTimeout body)
+    pub fn with_stop_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.stop_flag = flag;
+        self
+    }
+
+    /// The Parallel node this flow is a branch of, if any
+    pub fn fork(&self) -> Option<&Arc<ForkScope>> {
+        self.fork.as_ref()
+    }
+
+    /// Log sink that does not borrow the runtime (usable while `enigo()` is held)
+    pub fn logger(&self) -> impl Fn(String) + use<> {
+        let tx = self.tx.clone();
+        move |msg: String| {
+            let _ = tx.send(ExecutionEvent::Log(msg));
+        }
+    }
+
+    pub fn log(&self, msg: String) {
+        let _ = self.tx.send(ExecutionEvent::Log(msg));
+    }
+
+    /// Report an error that aborts the current flow
+    pub fn error(&self, msg: String) {
+        let _ = self.tx.send(ExecutionEvent::Error(msg));
+    }
+
+    /// The current chain must end: stop was requested, the watchdog ended
+    /// this flow, a failure is unwinding to a TryCatch, a Break / Continue /
+    /// Return is unwinding, or this branch lost the race to a Join
+    pub fn should_stop(&self) -> bool {
+        self.halted
+            || self.pending_error.is_some()
+            || self.jump.is_some()
+            || self.stop_flag.load(Ordering::Relaxed)
+            || self.fork.as_ref().is_some_and(|f| f.is_cancelled())
+    }
+
+    /// Whether a looping node must exit now. User stops are logged as
+    /// `"{name}: Stop requested by user"`, the end of an enclosing
+    /// WithTimeout as `"{name}: Interrupted by timeout"`.
+    pub fn loop_interrupted(&self, name: &str) -> bool {
+        if self.stop_flag.load(Ordering::Relaxed) {
+            if self.run_stop.load(Ordering::Relaxed) {
+                self.log(format!("{}: Stop requested by user", name));
+            } else {
+                self.log(format!("{}: Interrupted by timeout", name));
+            }
+            return true;
+        }
+        self.should_stop()
+    }
+
+    /// Sleep for `duration`, waking early once the flow must stop (user Stop,
+    /// or the end of an enclosing WithTimeout)
+    pub fn sleep(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        while !self.should_stop() {
+            let left = until.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            std::thread::sleep(left.min(SLEEP_POLL));
+        }
+    }
+
+    /// Whether the user (or the watchdog's Stop action) requested a stop
+    pub fn stop_requested(&self) -> bool {
+        self.stop_flag.load(Ordering::Relaxed)
+    }
+
+    /// Whether this flow was ended by a step or time limit
+    pub fn halted_by_limit(&self) -> bool {
+        self.halted
+    }
+
+    /// Check the step budget and time limit before running `node_id`.
+    /// Returns the flow to take instead of running the node, if a limit was
+    /// reached.
+    fn check_limits(&mut self, node_id: Uuid) -> Option<Flow> {
+        if self.budget.lifted.load(Ordering::Relaxed) {
+            return None;
+        }
+        let reason = match (self.settings.max_steps, self.settings.max_duration_secs) {
+            (Some(max), _) if self.budget.steps() >= max => {
+                format!("Step limit reached ({} steps)", max)
+            }
+            (_, Some(secs)) if self.budget.started.elapsed() >= Duration::from_secs(secs) => {
+                format!("Time limit reached ({}s)", secs)
+            }
+            _ => return None,
+        };
+
+        match self.settings.on_limit {
+            LimitAction::Stop => {
+                self.log(format!("Watchdog: {}. Stopping execution.", reason));
+                self.context.lock().unwrap().request_stop();
+                self.halted = true;
+                Some(Flow::End)
+            }
+            LimitAction::Log => {
+                self.log(format!("Watchdog: {}. Continuing without limit.", reason));
+                self.budget.lifted.store(true, Ordering::Relaxed);
+                None
+            }
+            LimitAction::Error => {
+                // Fails the node about to run; if its Error port or a TryCatch
+                // handles that, the run continues without the limit
+                if let Some(flow) = self.route_failure(node_id, format!("Watchdog: {}", reason)) {
+                    self.budget.lifted.store(true, Ordering::Relaxed);
+                    return Some(flow);
+                }
+                self.error(format!("Execution stopped: {}.", reason));
+                self.context.lock().unwrap().request_stop();
+                self.halted = true;
+                Some(Flow::End)
+            }
+        }
+    }
+
+    /// Input simulator for this flow, or `None` if it could not be created
+    pub fn enigo(&mut self) -> Option<&mut Enigo> {
+        if !self.enigo_initialized {
+            self.enigo_initialized = true;
+            self.enigo = Enigo::new(&Settings::default()).ok();
+            if self.enigo.is_none() {
+                self.log("Warning: Failed to initialize Input Simulator (Enigo). Mouse/Keyboard actions will fail.".into());
+            }
+        }
+        self.enigo.as_mut()
+    }
+
+    /// Evaluate a data input of a node. A [`NodeError`] from the data nodes
+    /// behind it makes the current node fail once it has run.
+    pub fn input(&self, node_id: Uuid, port: &str) -> anyhow::Result<VariableValue> {
+        let result = Interpreter::evaluate_input(&self.graph, node_id, port, &self.context);
+        if let Err(e) = &result
+            && let Some(node_error) = e.downcast_ref::<NodeError>()
+        {
+            self.input_error.replace(Some(node_error.0.clone()));
+        }
+        result
+    }
+
+    /// Report a failure of `node_id`: sets its `ErrorMessage` output and logs
+    /// the message, then follows `Error`, unwinds to the enclosing TryCatch,
+    /// or (unhandled) continues on `Next`.
+    pub fn fail(&mut self, node_id: Uuid, message: String) -> Flow {
+        // Supersedes any input failure of the same node
+        self.input_error.replace(None);
+        match self.route_failure(node_id, message) {
+            Some(flow) => flow,
+            None => self.flow_to(node_id, "Next"),
+        }
+    }
+
+    /// Handle a failure; `None` means unhandled.
+    fn route_failure(&mut self, node_id: Uuid, message: String) -> Option<Flow> {
+        self.log(message.clone());
+        self.context.lock().unwrap().set_output(
+            node_id,
+            "ErrorMessage",
+            VariableValue::String(message.clone()),
+        );
+
+        if let Some(next) = self.follow(node_id, "Error") {
+            return Some(Flow::Next(next));
+        }
+        if self.catch_depth > 0 {
+            self.pending_error = Some(message);
+            return Some(Flow::End);
+        }
+        None
+    }
+
+    /// Run the `Try` body of a TryCatch node. Returns the message of a failure
+    /// that happened inside it.
+    pub fn run_try(&mut self, node_id: Uuid, port: &str) -> Option<String> {
+        self.catch_depth += 1;
+        self.run_body(node_id, port);
+        self.catch_depth -= 1;
+        self.pending_error.take()
+    }
+
+    /// Run the user function `name` with `args` bound to its InputParam
+    /// nodes and return the values of its OutputParam nodes.
+    ///
+    /// `Err` holds the failure message: unknown function, recursion limit,
+    /// or a failure in the body that the body does not handle itself.
+    pub fn call_function(
+        &mut self,
+        name: &str,
+        args: HashMap<String, VariableValue>,
+    ) -> Result<HashMap<String, VariableValue>, String> {
+        let body = self.context.lock().unwrap().functions.get(name).cloned();
+        let Some(body) = body else {
+            return Err(format!("CallFunction: Unknown function '{}'", name));
+        };
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return Err(format!(
+                "CallFunction: '{}' exceeded {} nested calls",
+                name, MAX_CALL_DEPTH
+            ));
+        }
+        let Some(entry) = body
+            .nodes
+            .values()
+            .find(|n| matches!(n.node_type, NodeType::Entry))
+            .map(|n| n.id)
+        else {
+            return Err(format!("CallFunction: '{}' has no Entry node", name));
+        };
+
+        let mut frame = ExecutionContext::call_frame(&self.context, &body);
+        for node in body.nodes.values() {
+            if let NodeType::InputParam { name } = &node.node_type {
+                let value = args.get(name).cloned().unwrap_or(VariableValue::None);
+                frame.set_output(node.id, "Value", value);
+            }
+        }
+        let frame = Arc::new(Mutex::new(frame));
+
+        // The call itself acts as a TryCatch, so an unhandled failure ends the
+        // body and fails the call
+        let mut callee = FlowRuntime::new(body.clone(), frame.clone(), self.tx.clone());
+        callee.settings = self.settings.clone();
+        callee.catch_depth = 1;
+        callee.fork = self.fork.clone();
+        callee.call_depth = self.call_depth + 1;
+        callee.stop_flag = self.stop_flag.clone();
+        callee.run_stop = self.run_stop.clone();
+
+        // Nested one level deeper, so Step Over runs the whole call
+        if let Some(start) = callee.follow(entry, "Next") {
+            let _depth = DepthGuard::enter();
+            callee.run_chain(start);
+        }
+        self.halted |= callee.halted;
+        if let Some(message) = callee.pending_error.take() {
+            return Err(message);
+        }
+
+        Ok(body
+            .nodes
+            .values()
+            .filter_map(|node| match &node.node_type {
+                NodeType::OutputParam { name } => {
+                    let value = Interpreter::evaluate_input(&body, node.id, "Value", &frame)
+                        .unwrap_or(VariableValue::None);
+                    Some((name.clone(), value))
+                }
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Run the saved script `name` to completion with `args` written to its
+    /// variables, and return its variables afterwards.
+    ///
+    /// Only the script's OnStart / Event Tick flows run, one after another.
+    /// `Err` holds the failure message: unreadable script, a call cycle, or a
+    /// failure in the script when the call is inside a TryCatch.
+    pub fn call_script(
+        &mut self,
+        name: &str,
+        args: HashMap<String, VariableValue>,
+    ) -> Result<HashMap<String, VariableValue>, String> {
+        let name = name.trim_end_matches(".json");
+        let (mut chain, debug) = {
+            let ctx = self.context.lock().unwrap();
+            (ctx.scripts.clone(), ctx.debug.clone())
+        };
+        if chain.iter().any(|s| s == name) {
+            chain.push(name.to_string());
+            return Err(format!("CallScript: Cycle detected ({})", chain.join(" → ")));
+        }
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return Err(format!(
+                "CallScript: '{}' exceeded {} nested calls",
+                name, MAX_CALL_DEPTH
+            ));
+        }
+        let graph = BlueprintGraph::load(BlueprintGraph::script_path(name))
+            .map_err(|e| format!("CallScript: {:#}", e))?;
+        let graph = Arc::new(ExecutionGraph::new(graph));
+        let starts = Triggers::collect(&graph).start_nodes().to_vec();
+        if starts.is_empty() {
+            return Err(format!("CallScript: '{}' has no OnStart node", name));
+        }
+
+        let mut ctx = ExecutionContext::with_debug(debug);
+        ctx.stop_requested = self.stop_flag.clone();
+        ctx.functions = Arc::new(ExecutionGraph::functions(&graph));
+        chain.push(name.to_string());
+        ctx.scripts = chain;
+        ctx.budget = self.budget.clone();
+        for (var_name, var) in &graph.variables {
+            let value = args.get(var_name).unwrap_or(&var.initial_value);
+            ctx.variables.insert(var_name.clone(), value.clone());
+        }
+        let context = Arc::new(Mutex::new(ctx));
+
+        self.log(format!("CallScript: Running '{}'", name));
+        for start in starts {
+            // The script runs under this run's settings, budget and TryCatch
+            let mut callee = FlowRuntime::new(graph.clone(), context.clone(), self.tx.clone());
+            callee.settings = self.settings.clone();
+            callee.catch_depth = self.catch_depth;
+            callee.fork = self.fork.clone();
+            callee.call_depth = self.call_depth + 1;
+            callee.stop_flag = self.stop_flag.clone();
+            callee.run_stop = self.run_stop.clone();
+
+            if let Some(first) = callee.follow(start, "Next") {
+                let _depth = DepthGuard::enter();
+                callee.run_chain(first);
+            }
+            self.halted |= callee.halted;
+            if let Some(message) = callee.pending_error.take() {
+                return Err(message);
+            }
+            if self.should_stop() {
+                break;
+            }
+        }
+
+        let variables = context.lock().unwrap().variables.clone();
+        Ok(variables)
+    }
+
+    /// Whether an input port has an incoming connection
+    pub fn is_connected(&self, node_id: Uuid, port: &str) -> bool {
+        self.graph.source(node_id, port).is_some()
+    }
+
+    /// Follow an exec output to the node it connects to.
+    ///
+    /// Reaching the `Continue` input of a ForLoopAsync sets that loop's
+    /// continue signal and ends the branch instead. Reaching the `Reset`
+    /// input of a DoOnce / DoN reopens it and ends the branch.
+    pub fn follow(&self, node_id: Uuid, port: &str) -> Option<Uuid> {
+        let (to_node, to_port) = self.graph.target(node_id, port)?;
+
+        if let Some(target) = self.graph.nodes.get(&to_node) {
+            match (to_port, &target.node_type) {
+                ("Continue", NodeType::ForLoopAsync) => {
+                    self.context.lock().unwrap().update_node_state(to_node, |state| {
+                        *state = Some(VariableValue::Boolean(true));
+                    });
+                    return None;
+                }
+                // DoOnce opens (even with Start Closed), DoN counts from zero
+                ("Reset", NodeType::DoOnce) => {
+                    self.context.lock().unwrap().update_node_state(to_node, |state| {
+                        *state = Some(VariableValue::Boolean(false));
+                    });
+                    return None;
+                }
+                ("Reset", NodeType::DoN) => {
+                    self.context
+                        .lock()
+                        .unwrap()
+                        .update_node_state(to_node, |state| *state = None);
+                    return None;
+                }
+                _ => {}
+            }
+        }
+        Some(to_node)
+    }
+
+    /// Follow an exec output and turn it into the next [`Flow`] step
+    pub fn flow_to(&self, node_id: Uuid, port: &str) -> Flow {
+        self.follow(node_id, port).into()
+    }
+
+    /// End the current chain with a Break, Continue or Return. Break and
+    /// Continue outside any loop end the whole flow, like Return.
+    pub fn jump(&mut self, jump: Jump) -> Flow {
+        if jump != Jump::Return && self.loop_depth == 0 {
+            self.log(format!("{:?}: Not inside a loop, ending the flow", jump));
+        }
+        self.jump = Some(jump);
+        Flow::End
+    }
+
+    /// Run one iteration of a loop body connected to `port` of `node_id`.
+    ///
+    /// Returns the Break or Continue that ended the iteration early, which
+    /// is consumed here. Other reasons to stop are left for
+    /// [`loop_interrupted`](Self::loop_interrupted).
+    pub fn run_loop_body(&mut self, node_id: Uuid, port: &str) -> Option<Jump> {
+        self.loop_depth += 1;
+        self.run_body(node_id, port);
+        self.loop_depth -= 1;
+        match self.jump {
+            Some(jump @ (Jump::Break | Jump::Continue)) => {
+                self.jump = None;
+                Some(jump)
+            }
+            _ => None,
+        }
+    }
+
+    /// Run the chain connected to `port` of `node_id` to completion
+    /// (loop bodies, Sequence outputs). Nested one level deeper for stepping.
+    pub fn run_body(&mut self, node_id: Uuid, port: &str) {
+        if let Some(start) = self.follow(node_id, port) {
+            let _depth = DepthGuard::enter();
+            self.run_chain(start);
+        }
+    }
+
+    /// Run the body connected to `port` of `node_id`, cancelling it once
+    /// `timeout` has passed. Returns whether it timed out.
+    pub fn run_body_with_timeout(&mut self, node_id: Uuid, port: &str, timeout: Duration) -> bool {
+        let run_stop = self.stop_flag.clone();
+        let body_stop = Arc::new(AtomicBool::new(false));
+        let finished = Arc::new(AtomicBool::new(false));
+        let watcher = {
+            let (body_stop, finished) = (body_stop.clone(), finished.clone());
+            let deadline = Instant::now() + timeout;
+            std::thread::spawn(move || {
+                while !finished.load(Ordering::Relaxed) {
+                    if run_stop.load(Ordering::Relaxed) {
+                        body_stop.store(true, Ordering::Relaxed);
+                        return false;
+                    }
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    if left.is_zero() {
+                        body_stop.store(true, Ordering::Relaxed);
+                        return true;
+                    }
+                    std::thread::sleep(left.min(FORK_POLL));
+                }
+                false
+            })
+        };
+
+        let run_stop = std::mem::replace(&mut self.stop_flag, body_stop);
+        self.run_body(node_id, port);
+        self.stop_flag = run_stop;
+        finished.store(true, Ordering::Relaxed);
+        watcher.join().unwrap_or(false)
+    }
+
+    /// Execute nodes starting at `start` until the chain ends, stop is
+    /// requested, or a limit from the graph's settings ends the flow.
+    pub fn run_chain(&mut self, start: Uuid) {
+        let graph = self.graph.clone();
+        let mut current = start;
+
+        loop {
+            if self.should_stop() {
+                return;
+            }
+            match self.check_limits(current) {
+                Some(Flow::Next(next)) => {
+                    current = next;
+                    continue;
+                }
+                Some(Flow::End) => return,
+                None => {}
+            }
+            self.budget.steps.fetch_add(1, Ordering::Relaxed);
+
+            let node = match graph.nodes.get(&current) {
+                Some(n) => n,
+                None => return,
+            };
+
+            // Breakpoints / stepping (blocks while paused)
+            if !self.debug.before_node(current, &self.stop_flag, &self.tx) {
+                return;
+            }
+
+            // Notify UI that node is active
+            let _ = self.tx.send(ExecutionEvent::NodeActive(current));
+
+            // Input failures recorded by an enclosing node (a loop whose body
+            // runs now) belong to that node, not to this one
+            let outer_error = self.input_error.take();
+
+            let mut flow = match nodes::executor_for(&node.node_type) {
+                Some(executor) => executor.execute(self, node),
+                None => {
+                    self.log(format!("Skipping unsupported node {:?}", node.node_type));
+                    self.flow_to(current, "Next")
+                }
+            };
+
+            // A data node feeding this one failed (e.g. JSONParse on bad input)
+            if let Some(message) = self.input_error.take()
+                && self.pending_error.is_none()
+                && let Some(routed) = self.route_failure(current, message)
+            {
+                flow = routed;
+            }
+            if outer_error.is_some() {
+                self.input_error.replace(outer_error);
+            }
+
+            match flow {
+                Flow::Next(next) => current = next,
+                Flow::End => return,
+            }
+        }
+    }
+}
+
+/// Steps executed and start time of a run, shared by all of its flows and
+/// checked against the graph's [`ExecutionSettings`]
+#[derive(Debug)]
+pub struct RunBudget {
+    steps: AtomicU64,
+    started: Instant,
+    /// Set once a limit was reached and the run continues without limits
+    /// ([`LimitAction::Log`], or a handled [`LimitAction::Error`])
+    lifted: AtomicBool,
+}
+
+impl RunBudget {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            steps: AtomicU64::new(0),
+            started: Instant::now(),
+            lifted: AtomicBool::new(false),
+        })
+    }
+
+    /// Nodes executed so far by every flow of the run
+    pub fn steps(&self) -> u64 {
+        self.steps.load(Ordering::Relaxed)
+    }
+}
+
+/// Nested function calls allowed before a call fails (runaway recursion)
+const MAX_CALL_DEPTH: usize = 100;
+
+/// How often a waiting Parallel node checks the stop flag
+const FORK_POLL: Duration = Duration::from_millis(50);
+
+/// How often [`FlowRuntime::sleep`] checks the stop flag
+const SLEEP_POLL: Duration = Duration::from_millis(50);
+
+/// Branches started by one Parallel node.
+///
+/// Each branch calls [`arrive`](Self::arrive) when it reaches a Join and
+/// [`finish`](Self::finish) when its thread is done. The Parallel node blocks
+/// in [`wait`](Self::wait) until it can continue.
+pub struct ForkScope {
+    state: Mutex<ForkState>,
+    changed: Condvar,
+    cancelled: AtomicBool,
+}
+
+struct ForkState {
+    /// Branches whose thread has not finished yet
+    running: usize,
+    /// First Join reached, and whether it waits for every branch
+    joined: Option<(Uuid, bool)>,
+}
+
+impl ForkScope {
+    pub fn new(branches: usize) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(ForkState {
+                running: branches,
+                joined: None,
+            }),
+            changed: Condvar::new(),
+            cancelled: AtomicBool::new(false),
+        })
+    }
+
+    /// A branch reached the Join `join_id`. `wait_all` comes from the Join's
+    /// `Wait All` input; the first branch to arrive decides it.
+    pub fn arrive(&self, join_id: Uuid, wait_all: bool) {
+        self.state
+            .lock()
+            .unwrap()
+            .joined
+            .get_or_insert((join_id, wait_all));
+        self.changed.notify_all();
+    }
+
+    /// A branch thread is done, whether or not it reached a Join
+    pub fn finish(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.running = state.running.saturating_sub(1);
+        self.changed.notify_all();
+    }
+
+    /// Ask the remaining branches to end at their next node
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Block until the branches allow the Parallel node to continue. Returns
+    /// the Join to continue from, or `None` if no branch reached one or
+    /// `stopped` returned true (the branches are cancelled then).
+    ///
+    /// A Join waiting for any branch releases on the first arrival and cancels
+    /// the others; otherwise every branch must finish first.
+    pub fn wait(&self, stopped: impl Fn() -> bool) -> Option<Uuid> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            match state.joined {
+                Some((join_id, false)) => {
+                    self.cancel();
+                    return Some(join_id);
+                }
+                Some((join_id, true)) if state.running == 0 => return Some(join_id),
+                None if state.running == 0 => return None,
+                _ => {}
+            }
+            if stopped() {
+                self.cancel();
+                return None;
+            }
+            state = self.changed.wait_timeout(state, FORK_POLL).unwrap().0;
+        }
+    }
+}
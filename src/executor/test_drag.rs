@@ -1,6 +1,7 @@
 use crate::graph::{BlueprintGraph, Connection, Node, Port, VariableValue};
 use crate::node_types::NodeType;
 use crate::executor::Interpreter;
+use crate::executor::view::ExecutionGraph;
 use crate::executor::events::ExecutionEvent;
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -74,7 +75,7 @@ pub fn run_drag_test() {
 
     let (tx_dummy, _) = channel();
     Interpreter::execute_flow(
-        Arc::new(graph.clone()),
+        Arc::new(ExecutionGraph::new(graph.clone())),
         tick_id,
         context.clone(),
         tx_dummy.clone()
@@ -125,7 +126,7 @@ pub fn run_drag_test() {
 
     let (tx_dummy, rx_log) = channel();
     Interpreter::execute_flow(
-        Arc::new(graph2),
+        Arc::new(ExecutionGraph::new(graph2)),
         tick_id_2,
         context.clone(),
         tx_dummy.clone()
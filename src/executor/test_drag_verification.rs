@@ -2,6 +2,7 @@ use crate::graph::{BlueprintGraph, Connection, Node, Port, VariableValue};
 use crate::node_types::{NodeType, DataType};
 use crate::executor::ExecutionContext;
 use crate::executor::Interpreter;
+use crate::executor::view::ExecutionGraph;
 use crate::executor::events::ExecutionEvent;
 use std::sync::{Arc, Mutex};
 use std::sync::mpsc::channel;
@@ -171,7 +172,7 @@ pub fn run_drag_verification() {
     let context = Arc::new(Mutex::new(ExecutionContext::new()));
     
     thread::spawn(move || {
-        Interpreter::execute_flow(Arc::new(graph), tick_id, context, tx);
+        Interpreter::execute_flow(Arc::new(ExecutionGraph::new(graph)), tick_id, context, tx);
     });
 
     thread::spawn(move || {
@@ -0,0 +1,327 @@
+//! # Event Triggers
+//!
+//! Event entry nodes and how each one is scheduled during a run.
+//!
+//! - **Event Tick** / **OnStart**: run once when execution starts
+//! - **OnInterval**: runs, waits `Interval (ms)` and runs again until stopped
+//! - **OnVariableChanged**: runs each time the variable named by its
+//!   `Variable` input is written
+//! - **OnHotkey**: runs each time its key combination is pressed anywhere on
+//!   the system (see [`hotkeys`](super::hotkeys)), until stopped
+//! - **OnStop**: runs once after every other flow has ended, including after
+//!   Stop, so scripts can release keys or restore windows
+//!
+//! Every firing is a separate flow; all of them count against the run's step
+//! budget and time limit, except the OnStop cleanup, which gets a fresh
+//! budget so it can run after a limit stopped the run. The run lasts
+//! while OnStart / OnInterval / OnHotkey flows are running; variable listeners
+//! then handle the changes still queued and exit.
+
+use super::events::ExecutionEvent;
+use super::hotkeys::{self, Hotkey, KeyEvent};
+use super::runtime::RunBudget;
+use super::view::ExecutionGraph;
+use super::{ExecutionContext, Interpreter};
+use crate::graph::VariableValue;
+use crate::node_types::NodeType;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, channel};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// How often idle triggers check the stop flag
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Shortest allowed OnInterval period
+const MIN_INTERVAL_MS: u64 = 10;
+
+/// Reports writes of watched variables to the OnVariableChanged listener.
+/// Installed in [`ExecutionContext::variable_watch`] for the run.
+pub struct VariableWatch {
+    names: HashSet<String>,
+    tx: Sender<String>,
+}
+
+impl VariableWatch {
+    /// Queue a change if an OnVariableChanged node listens to `name`
+    pub fn notify(&self, name: &str) {
+        if self.names.contains(name) {
+            let _ = self.tx.send(name.to_string());
+        }
+    }
+}
+
+/// Enabled event nodes of a graph, grouped by how they are scheduled.
+#[derive(Default)]
+pub struct Triggers {
+    start: Vec<Uuid>,
+    interval: Vec<Uuid>,
+    variable: Vec<Uuid>,
+    hotkey: Vec<Uuid>,
+    stop: Vec<Uuid>,
+}
+
+impl Triggers {
+    pub fn collect(graph: &ExecutionGraph) -> Self {
+        let mut triggers = Self::default();
+        for node in graph.nodes.values().filter(|n| n.enabled) {
+            match &node.node_type {
+                NodeType::BlueprintFunction { name } if name == "Event Tick" => {
+                    triggers.start.push(node.id)
+                }
+                NodeType::OnStart => triggers.start.push(node.id),
+                NodeType::OnInterval => triggers.interval.push(node.id),
+                NodeType::OnVariableChanged => triggers.variable.push(node.id),
+                NodeType::OnHotkey => triggers.hotkey.push(node.id),
+                NodeType::OnStop => triggers.stop.push(node.id),
+                _ => {}
+            }
+        }
+        triggers
+    }
+
+    /// OnStart / Event Tick nodes
+    pub fn start_nodes(&self) -> &[Uuid] {
+        &self.start
+    }
+
+    /// No event would start a flow: OnVariableChanged only reacts to writes
+    /// made by another flow, and OnStop only runs after one
+    pub fn is_empty(&self) -> bool {
+        self.start.is_empty() && self.interval.is_empty() && self.hotkey.is_empty()
+    }
+
+    /// Run every trigger until the run is over, then the OnStop cleanup.
+    ///
+    /// Called on the run's supervisor thread. Returns once every flow has
+    /// ended; the event sink is released so receivers see the run finish.
+    pub fn run(
+        self,
+        graph: Arc<ExecutionGraph>,
+        context: Arc<Mutex<ExecutionContext>>,
+        tx: Sender<ExecutionEvent>,
+    ) {
+        let stop_flag = context.lock().unwrap().stop_requested.clone();
+
+        let listener = (!self.variable.is_empty()).then(|| {
+            let watchers = self.variable_names(&graph, &context);
+            let (watch_tx, watch_rx) = channel();
+            context.lock().unwrap().variable_watch = Some(VariableWatch {
+                names: watchers.iter().map(|(_, name)| name.clone()).collect(),
+                tx: watch_tx,
+            });
+            (watchers, watch_rx)
+        });
+
+        let mut producers = Vec::new();
+        for &id in &self.start {
+            let (graph, context, tx) = (graph.clone(), context.clone(), tx.clone());
+            producers.push(thread::spawn(move || {
+                Interpreter::execute_flow(graph, id, context, tx);
+            }));
+        }
+        for &id in &self.interval {
+            let (graph, context, tx) = (graph.clone(), context.clone(), tx.clone());
+            producers.push(thread::spawn(move || {
+                run_interval(graph, id, context, tx);
+            }));
+        }
+        if !self.hotkey.is_empty() {
+            let hotkey_nodes = self.hotkey.clone();
+            let (graph, context, tx) = (graph.clone(), context.clone(), tx.clone());
+            producers.push(thread::spawn(move || {
+                listen_hotkeys(graph, hotkey_nodes, context, tx);
+            }));
+        }
+
+        // Variable changes are handled on their own thread, one flow at a time
+        let producers_done = Arc::new(AtomicBool::new(false));
+        let listener = listener.map(|(watchers, watch_rx)| {
+            let (graph, context, tx) = (graph.clone(), context.clone(), tx.clone());
+            let done = producers_done.clone();
+            thread::spawn(move || listen_variables(graph, watchers, watch_rx, done, context, tx))
+        });
+
+        for t in producers {
+            let _ = t.join();
+        }
+        producers_done.store(true, Ordering::Relaxed);
+        if let Some(t) = listener {
+            let _ = t.join();
+        }
+        context.lock().unwrap().variable_watch = None;
+
+        // Cleanup runs even after Stop; stopping again aborts it
+        if !self.stop.is_empty() {
+            let was_stopped = stop_flag.swap(false, Ordering::Relaxed);
+            context.lock().unwrap().budget = RunBudget::new();
+            for &id in &self.stop {
+                if stop_flag.load(Ordering::Relaxed) {
+                    break;
+                }
+                let _ = tx.send(ExecutionEvent::Log("OnStop: Running cleanup".to_string()));
+                Interpreter::execute_flow(graph.clone(), id, context.clone(), tx.clone());
+            }
+            stop_flag.fetch_or(was_stopped, Ordering::Relaxed);
+        }
+
+        // Drop the context's event sender so the channel closes with this thread
+        context.lock().unwrap().events = None;
+    }
+
+    /// `(node, variable)` for every OnVariableChanged node
+    fn variable_names(
+        &self,
+        graph: &ExecutionGraph,
+        context: &Arc<Mutex<ExecutionContext>>,
+    ) -> Vec<(Uuid, String)> {
+        self.variable
+            .iter()
+            .map(|&id| {
+                let name = Interpreter::evaluate_input(graph, id, "Variable", context)
+                    .map(|v| Interpreter::to_string(&v))
+                    .unwrap_or_default();
+                (id, name)
+            })
+            .collect()
+    }
+}
+
+/// Fire an OnInterval flow every `Interval (ms)` until stop is requested.
+fn run_interval(
+    graph: Arc<ExecutionGraph>,
+    node_id: Uuid,
+    context: Arc<Mutex<ExecutionContext>>,
+    tx: Sender<ExecutionEvent>,
+) {
+    let stop_flag = context.lock().unwrap().stop_requested.clone();
+    let interval_ms = match Interpreter::evaluate_input(&graph, node_id, "Interval (ms)", &context) {
+        Ok(VariableValue::Integer(ms)) => ms.max(0) as u64,
+        Ok(VariableValue::Float(ms)) => ms.max(0.0) as u64,
+        _ => 1000,
+    }
+    .max(MIN_INTERVAL_MS);
+    let interval = Duration::from_millis(interval_ms);
+
+    let _ = tx.send(ExecutionEvent::Log(format!(
+        "OnInterval: Firing every {}ms",
+        interval_ms
+    )));
+
+    let mut count = 0i64;
+    while !stop_flag.load(Ordering::Relaxed) {
+        let fired_at = Instant::now();
+        context
+            .lock()
+            .unwrap()
+            .set_output(node_id, "Count", VariableValue::Integer(count));
+        Interpreter::execute_flow(graph.clone(), node_id, context.clone(), tx.clone());
+        count += 1;
+
+        // Wait out the rest of the period in short slices to stay responsive to Stop
+        while !stop_flag.load(Ordering::Relaxed) {
+            let elapsed = fired_at.elapsed();
+            if elapsed >= interval {
+                break;
+            }
+            thread::sleep((interval - elapsed).min(POLL_INTERVAL));
+        }
+    }
+}
+
+/// Run an OnHotkey flow each time its combination is pressed, until stop is
+/// requested. Flows run one at a time; presses during a flow are queued.
+fn listen_hotkeys(
+    graph: Arc<ExecutionGraph>,
+    nodes: Vec<Uuid>,
+    context: Arc<Mutex<ExecutionContext>>,
+    tx: Sender<ExecutionEvent>,
+) {
+    let stop_flag = context.lock().unwrap().stop_requested.clone();
+    let rx = hotkeys::subscribe();
+
+    let bindings: Vec<(Uuid, Hotkey)> = nodes
+        .into_iter()
+        .filter_map(|id| {
+            let input = |port: &str| Interpreter::evaluate_input(&graph, id, port, &context);
+            let flag = |port: &str| input(port).map(|v| Interpreter::to_bool(&v)).unwrap_or(false);
+            let key_name = input("Key")
+                .map(|v| Interpreter::to_string(&v))
+                .unwrap_or_default();
+
+            match Hotkey::new(&key_name, flag("Ctrl"), flag("Shift"), flag("Alt"), flag("Command")) {
+                Some(hotkey) => {
+                    let _ = tx.send(ExecutionEvent::Log(format!(
+                        "OnHotkey: Listening for {}",
+                        hotkey.label
+                    )));
+                    Some((id, hotkey))
+                }
+                None => {
+                    let _ = tx.send(ExecutionEvent::Error(format!(
+                        "OnHotkey: Unknown key '{}'",
+                        key_name
+                    )));
+                    None
+                }
+            }
+        })
+        .collect();
+    if bindings.is_empty() {
+        return;
+    }
+
+    let mut held = HashSet::new();
+    while !stop_flag.load(Ordering::Relaxed) {
+        let key = match rx.recv_timeout(POLL_INTERVAL) {
+            // Auto-repeat sends presses for a key already down
+            Ok(KeyEvent::Press(key)) if held.insert(key) => key,
+            Ok(KeyEvent::Press(_)) => continue,
+            Ok(KeyEvent::Release(key)) => {
+                held.remove(&key);
+                continue;
+            }
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        for (node_id, hotkey) in bindings.iter().filter(|(_, h)| h.matches(key, &held)) {
+            let _ = tx.send(ExecutionEvent::Log(format!("OnHotkey: {} pressed", hotkey.label)));
+            Interpreter::execute_flow(graph.clone(), *node_id, context.clone(), tx.clone());
+        }
+    }
+}
+
+/// Run the OnVariableChanged flows for each queued change until the
+/// producers have finished and the queue is empty, or stop is requested.
+fn listen_variables(
+    graph: Arc<ExecutionGraph>,
+    watchers: Vec<(Uuid, String)>,
+    rx: Receiver<String>,
+    producers_done: Arc<AtomicBool>,
+    context: Arc<Mutex<ExecutionContext>>,
+    tx: Sender<ExecutionEvent>,
+) {
+    let stop_flag = context.lock().unwrap().stop_requested.clone();
+
+    while !stop_flag.load(Ordering::Relaxed) {
+        let name = match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(name) => name,
+            Err(RecvTimeoutError::Timeout) if producers_done.load(Ordering::Relaxed) => break,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        for (node_id, _) in watchers.iter().filter(|(_, watched)| *watched == name) {
+            {
+                let mut ctx = context.lock().unwrap();
+                let value = ctx.variables.get(&name).cloned().unwrap_or(VariableValue::None);
+                ctx.set_output(*node_id, "Value", value);
+            }
+            Interpreter::execute_flow(graph.clone(), *node_id, context.clone(), tx.clone());
+        }
+    }
+}
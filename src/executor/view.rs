@@ -0,0 +1,100 @@
+//! # Execution View
+//!
+//! Immutable, indexed view of a [`BlueprintGraph`] built once when a run starts.
+//!
+//! The interpreter follows exec outputs and pulls data inputs on every step.
+//! Scanning `graph.connections` for each lookup makes tight loops scale with
+//! graph size, so the view keeps adjacency maps for both directions:
+//! - output `(node, port)` → connected inputs, in connection order
+//! - input `(node, port)` → the output feeding it
+//!
+//! The view dereferences to the underlying graph for node and variable access.
+//! Function bodies get their own view, collected in a [`FunctionLibrary`].
+
+use crate::graph::BlueprintGraph;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+use uuid::Uuid;
+
+type PortMap<T> = HashMap<Uuid, HashMap<String, T>>;
+
+/// Views of a graph's user function bodies, by function name
+pub type FunctionLibrary = HashMap<String, Arc<ExecutionGraph>>;
+
+pub struct ExecutionGraph {
+    graph: BlueprintGraph,
+    /// Output port → every input it connects to
+    outgoing: PortMap<Vec<(Uuid, String)>>,
+    /// Input port → the output it reads from
+    incoming: PortMap<(Uuid, String)>,
+}
+
+impl ExecutionGraph {
+    pub fn new(graph: BlueprintGraph) -> Self {
+        let mut outgoing: PortMap<Vec<(Uuid, String)>> = HashMap::new();
+        let mut incoming: PortMap<(Uuid, String)> = HashMap::new();
+
+        for conn in &graph.connections {
+            outgoing
+                .entry(conn.from_node)
+                .or_default()
+                .entry(conn.from_port.clone())
+                .or_default()
+                .push((conn.to_node, conn.to_port.clone()));
+            // An input reads from one output; the first connection wins
+            incoming
+                .entry(conn.to_node)
+                .or_default()
+                .entry(conn.to_port.clone())
+                .or_insert_with(|| (conn.from_node, conn.from_port.clone()));
+        }
+
+        Self {
+            graph,
+            outgoing,
+            incoming,
+        }
+    }
+
+    /// First input connected to an output port (the exec target for flow ports)
+    pub fn target(&self, node_id: Uuid, port: &str) -> Option<(Uuid, &str)> {
+        self.targets(node_id, port)
+            .first()
+            .map(|(id, port)| (*id, port.as_str()))
+    }
+
+    /// Every input connected to an output port
+    pub fn targets(&self, node_id: Uuid, port: &str) -> &[(Uuid, String)] {
+        self.outgoing
+            .get(&node_id)
+            .and_then(|ports| ports.get(port))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Index the body of every function of `graph`
+    pub fn functions(graph: &BlueprintGraph) -> FunctionLibrary {
+        graph
+            .functions
+            .iter()
+            .map(|f| (f.name.clone(), Arc::new(ExecutionGraph::new(f.body.clone()))))
+            .collect()
+    }
+
+    /// Output port feeding an input port, if connected
+    pub fn source(&self, node_id: Uuid, port: &str) -> Option<(Uuid, &str)> {
+        self.incoming
+            .get(&node_id)?
+            .get(port)
+            .map(|(id, port)| (*id, port.as_str()))
+    }
+}
+
+impl Deref for ExecutionGraph {
+    type Target = BlueprintGraph;
+
+    fn deref(&self) -> &BlueprintGraph {
+        &self.graph
+    }
+}
@@ -1,7 +1,10 @@
 use super::node_types::{DataType, NodeType};
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BlueprintGraph {
@@ -11,6 +14,117 @@ pub struct BlueprintGraph {
     /// Node groups for organizing nodes (UE5 BP-style grouping)
     #[serde(default)]
     pub groups: HashMap<Uuid, NodeGroup>,
+    /// Step budget and watchdog policy for runs of this graph
+    #[serde(default)]
+    pub settings: ExecutionSettings,
+    /// Times at which the scheduler runs this graph
+    #[serde(default)]
+    pub schedules: Vec<Schedule>,
+    /// User functions, called with CallFunction nodes
+    #[serde(default)]
+    pub functions: Vec<Function>,
+}
+
+/// A user function: a named subgraph called by CallFunction nodes.
+///
+/// The body starts at its Entry node and returns when that flow ends. Its
+/// InputParam nodes read the call's arguments and its OutputParam nodes
+/// provide the results. Variables first written inside the body are local
+/// to each call, so functions can recurse.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Function {
+    pub name: String,
+    pub body: BlueprintGraph,
+}
+
+impl Function {
+    /// Parameter names (InputParam nodes) and result names (OutputParam
+    /// nodes), each ordered top to bottom as placed in the body.
+    pub fn signature(&self) -> (Vec<String>, Vec<String>) {
+        let mut params = Vec::new();
+        let mut results = Vec::new();
+        for node in self.body.nodes.values() {
+            match &node.node_type {
+                NodeType::InputParam { name } => params.push((node.position, name.clone())),
+                NodeType::OutputParam { name } => results.push((node.position, name.clone())),
+                _ => {}
+            }
+        }
+        let ordered = |mut ports: Vec<((f32, f32), String)>| {
+            ports.sort_by(|(a, _), (b, _)| a.1.total_cmp(&b.1).then(a.0.total_cmp(&b.0)));
+            let mut names: Vec<String> = Vec::new();
+            for (_, name) in ports {
+                if !name.is_empty() && !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            names
+        };
+        (ordered(params), ordered(results))
+    }
+}
+
+/// Execution limits enforced by the interpreter (watchdog).
+///
+/// Both limits cover the whole run: steps count every node executed by any
+/// flow (event flows, loop bodies, calls, Parallel branches) and the time
+/// runs from the start of the run. Limits are checked before each node, so a long Delay or Wait node
+/// finishes before the time limit applies.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecutionSettings {
+    /// Maximum nodes executed by the whole run; `None` = unlimited
+    pub max_steps: Option<u64>,
+    /// Maximum wall-clock run time in seconds; `None` = unlimited
+    pub max_duration_secs: Option<u64>,
+    /// What happens when a limit is reached
+    pub on_limit: LimitAction,
+}
+
+impl Default for ExecutionSettings {
+    fn default() -> Self {
+        Self {
+            max_steps: None,
+            max_duration_secs: None,
+            on_limit: LimitAction::Error,
+        }
+    }
+}
+
+/// Action taken when a step or time limit is reached.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LimitAction {
+    /// Stop the whole run (like pressing Stop)
+    Stop,
+    /// Log a warning once and keep running
+    Log,
+    /// Fail the node about to run, like any node failure (Error port,
+    /// TryCatch, otherwise an execution error)
+    #[default]
+    Error,
+}
+
+impl LimitAction {
+    pub const ALL: [LimitAction; 3] = [LimitAction::Stop, LimitAction::Log, LimitAction::Error];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LimitAction::Stop => "Stop execution",
+            LimitAction::Log => "Log warning and continue",
+            LimitAction::Error => "Raise error",
+        }
+    }
+}
+
+/// When the scheduler launches a script. See [`crate::scheduler`] for the
+/// accepted formats.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Cron expression (`"30 8 * * 1-5"`) or interval
+    /// (`"every 15 minutes between 09:00 and 17:00"`)
+    pub spec: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
 }
 
 /// A visual group that can contain multiple nodes.
@@ -34,10 +148,192 @@ impl Default for BlueprintGraph {
             connections: Vec::new(),
             variables: HashMap::new(),
             groups: HashMap::new(),
+            settings: ExecutionSettings::default(),
+            schedules: Vec::new(),
+            functions: Vec::new(),
         }
     }
 }
 
+impl BlueprintGraph {
+    /// Parse a graph from the JSON format used by `scripts/*.json`.
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize the graph to JSON.
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Load a graph from a script file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read script {}", path.display()))?;
+        Self::from_json(&json).with_context(|| format!("Failed to parse script {}", path.display()))
+    }
+
+    /// Path of the saved script `name` (with or without `.json`)
+    pub fn script_path(name: &str) -> PathBuf {
+        PathBuf::from(format!("scripts/{}.json", name.trim_end_matches(".json")))
+    }
+
+    /// Ports of a CallScript node running this graph: one argument and one
+    /// result per variable, by name. New argument ports start with the
+    /// variable's initial value.
+    pub fn script_signature(&self) -> CallSignature {
+        let mut variables: Vec<&Variable> = self.variables.values().collect();
+        variables.sort_by(|a, b| a.name.cmp(&b.name));
+        (
+            variables
+                .iter()
+                .map(|v| (v.name.clone(), v.initial_value.clone()))
+                .collect(),
+            variables.iter().map(|v| v.name.clone()).collect(),
+        )
+    }
+
+    pub fn function(&self, name: &str) -> Option<&Function> {
+        self.functions.iter().find(|f| f.name == name)
+    }
+
+    /// Rename a function and every CallFunction node calling it
+    pub fn rename_function(&mut self, old: &str, new: &str) {
+        let rename_calls = |nodes: &mut HashMap<Uuid, Node>| {
+            for node in nodes.values_mut() {
+                if let NodeType::CallFunction { name } = &mut node.node_type
+                    && name == old
+                {
+                    *name = new.to_string();
+                }
+            }
+        };
+        rename_calls(&mut self.nodes);
+        for function in &mut self.functions {
+            if function.name == old {
+                function.name = new.to_string();
+            }
+            rename_calls(&mut function.body.nodes);
+        }
+    }
+
+    /// Give every CallFunction node (here and in function bodies) one input
+    /// per parameter and one output per result of the function it calls.
+    ///
+    /// Ports that still exist keep their values; connections to removed
+    /// ports are dropped. Calls to unknown functions are left as they are.
+    /// Returns whether anything changed.
+    pub fn sync_function_calls(&mut self) -> bool {
+        let signatures: HashMap<String, CallSignature> = self
+            .functions
+            .iter()
+            .map(|f| {
+                let (params, results) = f.signature();
+                let params = params
+                    .into_iter()
+                    .map(|p| (p, VariableValue::String("".into())))
+                    .collect();
+                (f.name.clone(), (params, results))
+            })
+            .collect();
+        let signature = |node_type: &NodeType| match node_type {
+            NodeType::CallFunction { name } => signatures.get(name),
+            _ => None,
+        };
+
+        let mut changed = sync_calls(self, &signature);
+        for function in &mut self.functions {
+            changed |= sync_calls(&mut function.body, &signature);
+        }
+        changed
+    }
+
+    /// Same as [`sync_function_calls`](Self::sync_function_calls) for
+    /// CallScript nodes, with the [`script_signature`](Self::script_signature)
+    /// of each script that could be loaded.
+    pub fn sync_script_calls(&mut self, scripts: &HashMap<String, CallSignature>) -> bool {
+        let signature = |node_type: &NodeType| match node_type {
+            NodeType::CallScript { name } => scripts.get(name),
+            _ => None,
+        };
+
+        let mut changed = sync_calls(self, &signature);
+        for function in &mut self.functions {
+            changed |= sync_calls(&mut function.body, &signature);
+        }
+        changed
+    }
+
+    /// Names of the scripts run by CallScript nodes, here and in function bodies
+    pub fn called_scripts(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        let bodies = std::iter::once(self).chain(self.functions.iter().map(|f| &f.body));
+        for node in bodies.flat_map(|g| g.nodes.values()) {
+            if let NodeType::CallScript { name } = &node.node_type
+                && !names.contains(name)
+            {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+}
+
+/// Ports of a call node: the parameters, each with the default value of a
+/// newly added input, and the results
+pub type CallSignature = (Vec<(String, VariableValue)>, Vec<String>);
+
+fn sync_calls<'a>(
+    graph: &mut BlueprintGraph,
+    signature: &dyn Fn(&NodeType) -> Option<&'a CallSignature>,
+) -> bool {
+    let mut changed = false;
+    for node in graph.nodes.values_mut() {
+        let Some((params, results)) = signature(&node.node_type) else {
+            continue;
+        };
+
+        let port = |name: &str, data_type: DataType, default_value: VariableValue| Port {
+            name: name.to_string(),
+            data_type,
+            default_value,
+        };
+        let any = || DataType::Custom("Any".into());
+        let mut inputs = vec![port("In", DataType::ExecutionFlow, VariableValue::None)];
+        for (param, initial) in params {
+            // Keep a value typed into the call node
+            let default_value = node
+                .inputs
+                .iter()
+                .find(|p| p.name == *param)
+                .map_or(initial.clone(), |p| p.default_value.clone());
+            inputs.push(port(param, any(), default_value));
+        }
+        let mut outputs = vec![port("Next", DataType::ExecutionFlow, VariableValue::None)];
+        outputs.extend(results.iter().map(|r| port(r, any(), VariableValue::None)));
+        if node.node_type.has_error_flow() {
+            outputs.push(port("Error", DataType::ExecutionFlow, VariableValue::None));
+            outputs.push(port("ErrorMessage", DataType::String, VariableValue::String("".into())));
+        }
+
+        let names = |ports: &[Port]| ports.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+        if names(&inputs) == names(&node.inputs) && names(&outputs) == names(&node.outputs) {
+            continue;
+        }
+
+        let id = node.id;
+        graph.connections.retain(|c| {
+            (c.to_node != id || inputs.iter().any(|p| p.name == c.to_port))
+                && (c.from_node != id || outputs.iter().any(|p| p.name == c.from_port))
+        });
+        node.inputs = inputs;
+        node.outputs = outputs;
+        changed = true;
+    }
+    changed
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Node {
     pub id: Uuid,
@@ -93,6 +389,9 @@ pub struct Variable {
     pub name: String,
     pub data_type: DataType,
     pub initial_value: VariableValue,
+    /// Keep the final value for the next run (see `executor::persistent`)
+    #[serde(default)]
+    pub persistent: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -102,7 +401,52 @@ pub enum VariableValue {
     Float(f64),
     String(String),
     Vector3(f32, f32, f32),
+    /// Screen position `(x, y)`
+    Point(i64, i64),
+    /// Screen area `(x, y, width, height)`, from its top-left corner
+    Rect(i64, i64, i64, i64),
     /// Array of values (for Module H: Data Operations)
     Array(Vec<VariableValue>),
+    /// String-keyed map of values, e.g. a parsed JSON object
+    Map(BTreeMap<String, VariableValue>),
+    /// Image held in memory, e.g. a screen capture. Only lives for a run:
+    /// it is never saved with a graph or a persistent variable.
+    #[serde(skip)]
+    Image(ImageValue),
     None, 
 }
+
+/// Image shared between the nodes it flows through, without copying pixels
+#[derive(Clone)]
+pub struct ImageValue(pub Arc<image::RgbaImage>);
+
+impl ImageValue {
+    pub fn new(image: image::RgbaImage) -> Self {
+        Self(Arc::new(image))
+    }
+}
+
+impl std::fmt::Debug for ImageValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Image({}x{})", self.0.width(), self.0.height())
+    }
+}
+
+impl VariableValue {
+    /// Human-readable type name (used by the Watch window)
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            VariableValue::Boolean(_) => "Boolean",
+            VariableValue::Integer(_) => "Integer",
+            VariableValue::Float(_) => "Float",
+            VariableValue::String(_) => "String",
+            VariableValue::Vector3(..) => "Vector3",
+            VariableValue::Point(..) => "Point",
+            VariableValue::Rect(..) => "Rect",
+            VariableValue::Array(_) => "Array",
+            VariableValue::Map(_) => "Map",
+            VariableValue::Image(_) => "Image",
+            VariableValue::None => "None",
+        }
+    }
+}
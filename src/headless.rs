@@ -0,0 +1,145 @@
+//! # Headless Runner
+//!
+//! Runs a saved blueprint without opening the eframe window, for use from
+//! cron jobs and shell pipelines:
+//!
+//! ```text
+//! egui_blueprint --run <script>
+//! ```
+//!
+//! `<script>` is either a path to a `.json` file or the name of a script in
+//! `scripts/` (with or without the `.json` extension).
+//!
+//! `--schedule <script>...` keeps running and launches each script at the
+//! times declared in its schedules (see [`egui_blueprint::scheduler`]).
+//!
+//! ## Exit Codes
+//! - `0`: all flows finished normally
+//! - `1`: the script could not be loaded, or a flow reported an error
+//! - `2`: execution was stopped before finishing
+
+use anyhow::Result;
+use egui_blueprint::executor::{RunOptions, hotkeys, persistent};
+use egui_blueprint::scheduler::{self, ScheduledScript};
+use egui_blueprint::{BlueprintGraph, ExecutionEvent, Interpreter, NodeType};
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_STOPPED: i32 = 2;
+
+/// Resolve a script argument to a file path.
+///
+/// An existing path is used as-is; otherwise the name is looked up in `scripts/`.
+fn resolve_script_path(script: &str) -> PathBuf {
+    let direct = PathBuf::from(script);
+    if direct.is_file() {
+        return direct;
+    }
+    let name = script.trim_end_matches(".json");
+    PathBuf::from(format!("scripts/{}.json", name))
+}
+
+/// Load a blueprint graph from a script name or path.
+pub fn load_graph(script: &str) -> Result<BlueprintGraph> {
+    BlueprintGraph::load(resolve_script_path(script))
+}
+
+/// Run a script to completion, printing logs to stdout and errors to stderr.
+/// Returns the process exit code.
+pub fn run(script: &str) -> i32 {
+    let graph = match load_graph(script) {
+        Ok(graph) => graph,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            return EXIT_FAILED;
+        }
+    };
+
+    // Without the GUI nothing else feeds global key events to OnHotkey nodes
+    let uses_hotkeys = graph
+        .nodes
+        .values()
+        .any(|n| n.enabled && n.node_type == NodeType::OnHotkey);
+    if uses_hotkeys {
+        std::thread::spawn(|| {
+            if let Err(error) = hotkeys::listen() {
+                eprintln!("Error: Global hotkey listener failed: {:?}", error);
+            }
+        });
+    }
+
+    let (tx, rx) = channel();
+    let options = RunOptions {
+        state_file: Some(persistent::state_path(&resolve_script_path(script))),
+        script: Some(script.to_string()),
+        ..Default::default()
+    };
+    let handle = Interpreter::spawn_with_options(&graph, tx, options);
+
+    // The channel closes once every flow thread has dropped its sender
+    let mut failed = false;
+    while let Ok(event) = rx.recv() {
+        match event {
+            ExecutionEvent::Log(msg) => println!("{}", msg),
+            ExecutionEvent::Error(msg) => {
+                eprintln!("Error: {}", msg);
+                failed = true;
+            }
+            ExecutionEvent::NodeActive(_)
+            | ExecutionEvent::NodeInactive(_)
+            | ExecutionEvent::Paused(_)
+            | ExecutionEvent::Resumed
+            | ExecutionEvent::VariableChanged(..)
+            | ExecutionEvent::OutputChanged(..)
+            | ExecutionEvent::Finished => {}
+        }
+    }
+
+    if handle.was_stopped() {
+        EXIT_STOPPED
+    } else if failed {
+        EXIT_FAILED
+    } else {
+        EXIT_OK
+    }
+}
+
+/// Serve the schedules of `scripts` until the process is terminated.
+/// Returns an exit code only if scheduling could not start.
+pub fn schedule(scripts: &[String]) -> i32 {
+    let mut scheduled = Vec::new();
+    for script in scripts {
+        match load_graph(script) {
+            Ok(graph) => scheduled.push(ScheduledScript {
+                name: script.trim_end_matches(".json").to_string(),
+                graph,
+                state_file: Some(persistent::state_path(&resolve_script_path(script))),
+            }),
+            Err(e) => {
+                eprintln!("Error: {:#}", e);
+                return EXIT_FAILED;
+            }
+        }
+    }
+
+    let (tx, rx) = channel();
+    let _handle = match scheduler::start(scheduled, tx) {
+        Ok(handle) => handle,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            return EXIT_FAILED;
+        }
+    };
+
+    // Runs forever; the scheduler holds the sender while it is alive
+    for event in rx {
+        match event {
+            ExecutionEvent::Log(msg) => println!("{}", msg),
+            ExecutionEvent::Error(msg) => eprintln!("Error: {}", msg),
+            _ => {}
+        }
+    }
+    EXIT_OK
+}
@@ -0,0 +1,34 @@
+//! # egui-blueprint
+//!
+//! Visual scripting library: the graph model, the interpreter and the node
+//! editor widget. The `egui_blueprint` binary is a thin client of this crate.
+//!
+//! ## Embedding
+//! ```no_run
+//! use egui_blueprint::{BlueprintGraph, ExecutionEvent, Interpreter};
+//! use std::sync::mpsc::channel;
+//!
+//! let graph = BlueprintGraph::load("scripts/my_script.json")?;
+//! let (tx, rx) = channel();
+//! let handle = Interpreter::spawn(&graph, tx);
+//! for event in rx {
+//!     if let ExecutionEvent::Log(msg) = event {
+//!         println!("{}", msg);
+//!     }
+//! }
+//! let variables = handle.join();
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod editor;
+pub mod executor;
+pub mod graph;
+pub mod history;
+pub mod node_types;
+pub mod recorder;
+pub mod scheduler;
+
+pub use executor::events::ExecutionEvent;
+pub use executor::{ExecutionContext, ExecutionHandle, Interpreter};
+pub use graph::{BlueprintGraph, VariableValue};
+pub use node_types::{DataType, NodeType};
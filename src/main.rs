@@ -1,17 +1,14 @@
-mod editor;
-mod executor;
-mod graph;
-mod history;
-mod node_types;
-mod recorder;
+mod headless;
 
 use chrono::Local;
+use egui_blueprint::{editor, executor, graph, history, node_types, recorder, scheduler};
 use editor::GraphEditor;
 use eframe::egui;
 use graph::{BlueprintGraph, Node, Port};
 use history::UndoStack;
 use node_types::{DataType, NodeType};
 use rdev;
+use executor::debug::DebugCommand;
 use executor::events::ExecutionEvent;
 use std::sync::mpsc::Receiver;
 use sysinfo::{Pid, ProcessesToUpdate, System};
@@ -19,6 +16,25 @@ use uuid::Uuid;
 
 fn main() -> eframe::Result<()> {
     env_logger::init();
+
+    // Headless mode: `--run <script>` executes a saved blueprint without the window
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|a| a == "--run") {
+        let Some(script) = args.get(pos + 1) else {
+            eprintln!("Usage: {} --run <script>", args[0]);
+            std::process::exit(headless::EXIT_FAILED);
+        };
+        std::process::exit(headless::run(script));
+    }
+    if let Some(pos) = args.iter().position(|a| a == "--schedule") {
+        let scripts = &args[pos + 1..];
+        if scripts.is_empty() {
+            eprintln!("Usage: {} --schedule <script>...", args[0]);
+            std::process::exit(headless::EXIT_FAILED);
+        }
+        std::process::exit(headless::schedule(scripts));
+    }
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([1200.0, 800.0]),
         ..Default::default()
@@ -48,6 +64,8 @@ struct MyApp {
     log_receiver: Option<Receiver<ExecutionEvent>>,
     /// Stop handle for force stopping execution
     stop_handle: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    /// Pause / continue / step commands for the running graph
+    debug_tx: Option<std::sync::mpsc::Sender<DebugCommand>>,
     // Debug window state
     system: System,
     frame_times: Vec<f32>,
@@ -73,6 +91,32 @@ struct MyApp {
     last_recorded_event_type: Option<rdev::EventType>,
     // Cursor info overlay - shows position and pixel color
     show_cursor_info: bool,
+    // Execution settings (step budget / watchdog) window
+    show_exec_settings: bool,
+    // Schedules window and the scheduler serving the current script
+    show_schedules: bool,
+    scheduler: Option<scheduler::SchedulerHandle>,
+    /// Logs of scheduled runs; disconnects when the scheduler stops
+    scheduler_rx: Option<Receiver<ExecutionEvent>>,
+    // Watch window state
+    show_watch_window: bool,
+    /// Latest value of every variable reported by the interpreter
+    watch_values: std::collections::BTreeMap<String, graph::VariableValue>,
+    /// Latest value of every node output port reported by the interpreter
+    watch_outputs: std::collections::BTreeMap<(Uuid, String), graph::VariableValue>,
+    /// Node outputs pinned to the main watch list
+    watch_pinned: std::collections::BTreeSet<(Uuid, String)>,
+    // Functions window
+    show_functions: bool,
+    /// Function whose body is shown in the canvas instead of the main graph
+    editing_function: Option<String>,
+    /// Ports of the scripts run by CallScript nodes
+    script_signatures: editor::functions::ScriptSignatures,
+    // Variables window, with the values stored for persistent variables
+    show_variables: bool,
+    /// Contents of the script's state file; `None` until (re)loaded
+    stored_state: Option<std::collections::HashMap<String, graph::VariableValue>>,
+    new_variable: (String, String),
 }
 
 impl Default for MyApp {
@@ -85,6 +129,9 @@ impl Default for MyApp {
             let mut meta_pressed = false;
             
             if let Err(error) = rdev::listen(move |event| {
+                // Feed OnHotkey events of running graphs
+                executor::hotkeys::publish(&event.event_type);
+
                 match event.event_type {
                     rdev::EventType::KeyPress(key) => {
                         match key {
@@ -138,6 +185,7 @@ impl Default for MyApp {
             undo_stack: UndoStack::default(),
             log_receiver: None,
             stop_handle: None,
+            debug_tx: None,
             system: System::new_all(),
             frame_times: Vec::with_capacity(120),
             last_frame_time: std::time::Instant::now(),
@@ -155,6 +203,20 @@ impl Default for MyApp {
             global_stop_rx: stop_rx,
             last_recorded_event_type: None,
             show_cursor_info: false,
+            show_exec_settings: false,
+            show_schedules: false,
+            scheduler: None,
+            scheduler_rx: None,
+            show_watch_window: false,
+            watch_values: std::collections::BTreeMap::new(),
+            watch_outputs: std::collections::BTreeMap::new(),
+            watch_pinned: std::collections::BTreeSet::new(),
+            show_functions: false,
+            editing_function: None,
+            script_signatures: Default::default(),
+            show_variables: false,
+            stored_state: None,
+            new_variable: (String::new(), String::new()),
         };
         let _ = std::fs::create_dir_all("scripts");
         // Load settings first (may auto-load last script)
@@ -214,8 +276,38 @@ impl MyApp {
         }
     }
 
+    /// Sidecar file with the persistent variables of the current script
+    fn state_file(&self) -> std::path::PathBuf {
+        executor::persistent::state_path(&BlueprintGraph::script_path(&self.script_name))
+    }
+
+    /// Move the editor's selection out of the main graph, or out of the body
+    /// of function `body_index`, into a new function of the script
+    fn collapse_selection(&mut self, body_index: Option<usize>) {
+        self.editor.collapse_requested = false;
+        let name = editor::functions::free_function_name(&self.graph, "Function");
+        let graph = match body_index {
+            Some(index) => &mut self.graph.functions[index].body,
+            None => &mut self.graph,
+        };
+        match editor::functions::collapse_to_function(graph, &self.editor.selected_nodes, &name) {
+            Ok(function) => {
+                self.logs.push(format!(
+                    "[System] Collapsed {} nodes into '{}'",
+                    self.editor.selected_nodes.len(),
+                    name
+                ));
+                self.graph.functions.push(function);
+                self.graph.sync_function_calls();
+                self.undo_stack.push(&self.graph);
+                self.editor.selected_nodes.clear();
+            }
+            Err(e) => self.logs.push(format!("[System] Cannot collapse selection: {}", e)),
+        }
+    }
+
     fn add_test_nodes(&mut self) {
-        use crate::graph::VariableValue;
+        use graph::VariableValue;
         let id1 = Uuid::new_v4();
         self.graph.nodes.insert(
             id1,
@@ -328,7 +420,7 @@ impl MyApp {
     
     /// Create a FindImage node pre-filled with the given image path
     fn create_find_image_node(&mut self, image_path: &str) {
-        use crate::graph::VariableValue;
+        use graph::VariableValue;
         
         // Get ports for FindImage node
         let (mut inputs, outputs) = GraphEditor::get_ports_for_type(&NodeType::FindImage);
@@ -379,6 +471,9 @@ impl eframe::App for MyApp {
         // Use a small interval (100ms) to reduce CPU usage while staying responsive
         if self.editor.recorder.is_recording() || self.log_receiver.is_some() {
             ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        } else if self.scheduler_rx.is_some() {
+            // Scheduled runs happen on their own thread; refresh logs and the panel
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
         }
         
         // Check for global F3 hotkey to force stop execution
@@ -389,11 +484,38 @@ impl eframe::App for MyApp {
             }
             self.stop_handle = None;
             self.log_receiver = None;
+            self.debug_tx = None;
+            self.editor.paused_node = None;
         }
+
+        // Debugger hotkeys: F5 continue, F10 step over, F11 step into
+        if let Some(tx) = &self.debug_tx {
+            let command = ctx.input(|i| {
+                if i.key_pressed(egui::Key::F5) {
+                    Some(DebugCommand::Continue)
+                } else if i.key_pressed(egui::Key::F10) {
+                    Some(DebugCommand::StepOver)
+                } else if i.key_pressed(egui::Key::F11) {
+                    Some(DebugCommand::StepInto)
+                } else {
+                    None
+                }
+            });
+            if let Some(command) = command {
+                if self.editor.paused_node.is_some() {
+                    let _ = tx.send(command);
+                }
+            }
+            // Forward breakpoints toggled during the run
+            if self.editor.breakpoints_changed {
+                let _ = tx.send(DebugCommand::SetBreakpoints(self.editor.breakpoints.clone()));
+            }
+        }
+        self.editor.breakpoints_changed = false;
         
         // Verification Test Trigger (Added for Drag Debugging)
         if ctx.input(|i| i.key_pressed(egui::Key::F6)) {
-             crate::executor::test_drag_verification::run_drag_verification();
+             executor::test_drag_verification::run_drag_verification();
         }
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
@@ -464,6 +586,7 @@ impl eframe::App for MyApp {
                 });
                 if ui.button("New").clicked() {
                     self.graph = graph::BlueprintGraph::default();
+                    self.editing_function = None;
                     self.script_name = "untitled".to_string();
                     self.undo_stack = UndoStack::default();
                     self.logs.push("[System] New script created.".to_string());
@@ -497,9 +620,19 @@ impl eframe::App for MyApp {
                 if ui.button("▶ Run").clicked() {
                     log::info!("Running graph (async)...");
                     self.start_time = std::time::Instant::now();
-                    let (rx, stop_handle) = executor::Interpreter::run_async_with_stop(&self.graph);
+                    let (tx, rx) = std::sync::mpsc::channel();
+                    let options = executor::RunOptions {
+                        breakpoints: self.editor.breakpoints.clone(),
+                        state_file: Some(self.state_file()),
+                        script: Some(self.script_name.clone()),
+                    };
+                    let handle = executor::Interpreter::spawn_with_options(&self.graph, tx, options);
                     self.log_receiver = Some(rx);
-                    self.stop_handle = Some(stop_handle);
+                    self.stop_handle = Some(handle.stop_handle());
+                    self.debug_tx = Some(handle.debug_sender());
+                    self.editor.paused_node = None;
+                    self.watch_values.clear();
+                    self.watch_outputs.clear();
                     self.logs
                         .push("[System] Async Execution Started".to_string());
                 }
@@ -513,6 +646,22 @@ impl eframe::App for MyApp {
                         self.stop_handle = None;
                     }
                 }
+                // Debugger controls - only while execution is running
+                if let Some(tx) = &self.debug_tx {
+                    if self.editor.paused_node.is_some() {
+                        if ui.button("▶ Continue").on_hover_text("Continue (F5)").clicked() {
+                            let _ = tx.send(DebugCommand::Continue);
+                        }
+                        if ui.button("⤼ Step Over").on_hover_text("Step to the next node (F10)").clicked() {
+                            let _ = tx.send(DebugCommand::StepOver);
+                        }
+                        if ui.button("⤵ Step Into").on_hover_text("Step into loop bodies (F11)").clicked() {
+                            let _ = tx.send(DebugCommand::StepInto);
+                        }
+                    } else if ui.button("⏸ Pause").clicked() {
+                        let _ = tx.send(DebugCommand::Pause);
+                    }
+                }
                 // ui.separator();
                 // if ui.button("Debug").clicked() {
                 //     self.show_debug_window = !self.show_debug_window;
@@ -578,9 +727,33 @@ impl eframe::App for MyApp {
                 if ui.button("Debug").clicked() {
                     self.show_debug_window = !self.show_debug_window;
                 }
+                if ui.button("𝑥").on_hover_text("Variables (initial and persistent values)").clicked() {
+                    self.show_variables = !self.show_variables;
+                    self.stored_state = None;
+                }
+                if ui.button("👁 Watch").on_hover_text("Live variable values").clicked() {
+                    self.show_watch_window = !self.show_watch_window;
+                }
+                if ui.button("⏱").on_hover_text("Execution Settings (step budget, time limit)").clicked() {
+                    self.show_exec_settings = !self.show_exec_settings;
+                }
+                let schedule_icon = if self.scheduler.is_some() { "⏰ ●" } else { "⏰" };
+                if ui.button(schedule_icon).on_hover_text("Schedules (run this script at set times)").clicked() {
+                    self.show_schedules = !self.show_schedules;
+                }
+                if ui.button("ƒ").on_hover_text("Functions").clicked() {
+                    self.show_functions = !self.show_functions;
+                }
                 if ui.button("🎨").on_hover_text("Style Settings").clicked() {
                     self.show_style_window = !self.show_style_window;
                 }
+                if let Some(name) = &self.editing_function {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::from_rgb(100, 160, 230), format!("ƒ Editing: {}", name));
+                    if ui.button("⬅ Main graph").clicked() {
+                        self.editing_function = None;
+                    }
+                }
             });
         });
         
@@ -809,7 +982,7 @@ impl eframe::App for MyApp {
                     }
 
                     // Auto-Connect
-                    self.graph.connections.push(crate::graph::Connection {
+                    self.graph.connections.push(graph::Connection {
                         from_node: prev_id,
                         from_port: "Next".to_string(),
                         to_node: node.id,
@@ -845,6 +1018,26 @@ impl eframe::App for MyApp {
             }
         }
 
+        // Drain logs of scheduled runs
+        if let Some(rx) = &self.scheduler_rx {
+            let time_str = Local::now().format("%H:%M:%S").to_string();
+            loop {
+                match rx.try_recv() {
+                    Ok(ExecutionEvent::Log(msg)) => self.logs.push(format!("[{}] {}", time_str, msg)),
+                    Ok(ExecutionEvent::Error(msg)) => {
+                        self.logs.push(format!("[{}] [Error] {}", time_str, msg))
+                    }
+                    Ok(_) => {}
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        self.scheduler_rx = None;
+                        self.scheduler = None;
+                        break;
+                    }
+                }
+            }
+        }
+
         // Drain logs from async threads and detect execution completion
         if let Some(rx) = &self.log_receiver {
             let mut channel_closed = false;
@@ -859,6 +1052,30 @@ impl eframe::App for MyApp {
                         ExecutionEvent::NodeActive(node_id) => {
                             self.editor.node_execution_times.insert(node_id, std::time::Instant::now());
                         }
+                        ExecutionEvent::Error(msg) => {
+                            let now = Local::now();
+                            let time_str = now.format("%H:%M:%S").to_string();
+                            self.logs.push(format!("[{}] [Error] {}", time_str, msg));
+                        }
+                        ExecutionEvent::Paused(node_id) => {
+                            self.editor.paused_node = Some(node_id);
+                            let name = self
+                                .graph
+                                .nodes
+                                .get(&node_id)
+                                .map(|n| n.display_name.clone().unwrap_or_else(|| format!("{:?}", n.node_type)))
+                                .unwrap_or_default();
+                            self.logs.push(format!("[Debug] Paused before {}", name));
+                        }
+                        ExecutionEvent::Resumed => {
+                            self.editor.paused_node = None;
+                        }
+                        ExecutionEvent::VariableChanged(name, value) => {
+                            self.watch_values.insert(name, value);
+                        }
+                        ExecutionEvent::OutputChanged(node_id, port, value) => {
+                            self.watch_outputs.insert((node_id, port), value);
+                        }
                         ExecutionEvent::NodeInactive(_) => {} // Optional future use
                         ExecutionEvent::Finished => {
                             // Can be used if we send explicit finish event
@@ -875,6 +1092,10 @@ impl eframe::App for MyApp {
                 // Execution thread has finished - clear log receiver and stop handle
                 self.log_receiver = None;
                 self.stop_handle = None;
+                self.debug_tx = None;
+                self.editor.paused_node = None;
+                // The run has written its persistent variables
+                self.stored_state = None;
                 // keep execution times for fade out
                 self.logs.push("[System] Execution Completed".to_string());
             }
@@ -949,6 +1170,464 @@ impl eframe::App for MyApp {
                 });
         }
 
+        // Watch Window - live variable values from the running graph
+        if self.show_watch_window {
+            let mut show_watch_window = self.show_watch_window;
+            egui::Window::new("👁 Watch")
+                .open(&mut show_watch_window)
+                .resizable(true)
+                .default_width(320.0)
+                .show(ctx, |ui| {
+                    // Label node outputs as "Node.Port"
+                    let output_label = |(node_id, port): &(Uuid, String)| -> String {
+                        match self.graph.nodes.get(node_id) {
+                            Some(node) => {
+                                let name = node
+                                    .display_name
+                                    .clone()
+                                    .unwrap_or_else(|| format!("{:?}", node.node_type));
+                                format!("{}.{}", name, port)
+                            }
+                            None => port.clone(),
+                        }
+                    };
+
+                    // User variables: graph-defined ones first, then any set at runtime
+                    let mut names: Vec<&String> = self
+                        .watch_values
+                        .keys()
+                        .filter(|k| !k.starts_with("__"))
+                        .collect();
+                    for name in self.graph.variables.keys() {
+                        if !self.watch_values.contains_key(name) {
+                            names.push(name);
+                        }
+                    }
+                    names.sort();
+                    let mut rows: Vec<(String, Option<&graph::VariableValue>)> = names
+                        .into_iter()
+                        .map(|name| {
+                            let value = self
+                                .watch_values
+                                .get(name)
+                                .or_else(|| self.graph.variables.get(name).map(|v| &v.initial_value));
+                            (name.clone(), value)
+                        })
+                        .collect();
+                    for key in &self.watch_pinned {
+                        rows.push((output_label(key), self.watch_outputs.get(key)));
+                    }
+
+                    egui::Grid::new("watch_grid")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Name");
+                            ui.strong("Type");
+                            ui.strong("Value");
+                            ui.end_row();
+                            for (label, value) in &rows {
+                                ui.label(label);
+                                match value {
+                                    Some(v) => {
+                                        ui.label(v.type_name());
+                                        ui.label(executor::helpers::to_string(v));
+                                    }
+                                    None => {
+                                        ui.label("-");
+                                        ui.label("-");
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+
+                    ui.separator();
+                    egui::CollapsingHeader::new("Node Outputs")
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                                let outputs: Vec<(Uuid, String)> =
+                                    self.watch_outputs.keys().cloned().collect();
+                                if outputs.is_empty() {
+                                    ui.label("No node outputs yet - run the graph first.");
+                                }
+                                for key in outputs {
+                                    let mut pinned = self.watch_pinned.contains(&key);
+                                    if ui.checkbox(&mut pinned, output_label(&key)).on_hover_text("Pin to watch list").changed() {
+                                        if pinned {
+                                            self.watch_pinned.insert(key);
+                                        } else {
+                                            self.watch_pinned.remove(&key);
+                                        }
+                                    }
+                                }
+                            });
+                        });
+                });
+            self.show_watch_window = show_watch_window;
+        }
+
+        // Variables Window - declared variables, persistence and stored values
+        if self.show_variables {
+            let mut show_variables = self.show_variables;
+            let mut changed = false;
+            let state_file = self.state_file();
+            let stored = self
+                .stored_state
+                .get_or_insert_with(|| executor::persistent::load(&state_file).unwrap_or_default());
+            let mut reset: Option<Option<String>> = None;
+            egui::Window::new("𝑥 Variables")
+                .open(&mut show_variables)
+                .resizable(true)
+                .default_width(420.0)
+                .show(ctx, |ui| {
+                    let mut names: Vec<String> = self.graph.variables.keys().cloned().collect();
+                    names.sort();
+                    let mut remove = None;
+                    egui::Grid::new("variables_grid")
+                        .num_columns(5)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Name");
+                            ui.strong("Initial");
+                            ui.strong("Persistent");
+                            ui.strong("Stored");
+                            ui.end_row();
+                            for name in &names {
+                                let Some(var) = self.graph.variables.get_mut(name) else {
+                                    continue;
+                                };
+                                ui.label(name);
+                                ui.label(executor::helpers::to_string(&var.initial_value))
+                                    .on_hover_text(var.initial_value.type_name());
+                                if ui.checkbox(&mut var.persistent, "").on_hover_text("Keep the final value for the next run").changed() {
+                                    changed = true;
+                                }
+                                match stored.get(name) {
+                                    Some(value) => {
+                                        ui.label(executor::helpers::to_string(value));
+                                    }
+                                    None => {
+                                        ui.weak("-");
+                                    }
+                                }
+                                ui.horizontal(|ui| {
+                                    if ui
+                                        .add_enabled(stored.contains_key(name), egui::Button::new("↺").small())
+                                        .on_hover_text("Reset to the initial value")
+                                        .clicked()
+                                    {
+                                        reset = Some(Some(name.clone()));
+                                    }
+                                    if ui.small_button("🗑").on_hover_text("Remove variable").clicked() {
+                                        remove = Some(name.clone());
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                    if let Some(name) = remove {
+                        self.graph.variables.remove(&name);
+                        changed = true;
+                    }
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        let (name, initial) = &mut self.new_variable;
+                        ui.add(egui::TextEdit::singleline(name).hint_text("Name").desired_width(120.0));
+                        ui.add(egui::TextEdit::singleline(initial).hint_text("Initial value").desired_width(120.0));
+                        let valid = !name.trim().is_empty() && !self.graph.variables.contains_key(name.trim());
+                        if ui.add_enabled(valid, egui::Button::new("➕ Add")).clicked() {
+                            let initial_value = parse_initial_value(initial.trim());
+                            let name = name.trim().to_string();
+                            self.graph.variables.insert(
+                                name.clone(),
+                                graph::Variable {
+                                    name,
+                                    data_type: data_type_of(&initial_value),
+                                    initial_value,
+                                    persistent: false,
+                                },
+                            );
+                            self.new_variable = (String::new(), String::new());
+                            changed = true;
+                        }
+                    });
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.weak(format!("Stored in {}", state_file.display()));
+                        if ui.add_enabled(!stored.is_empty(), egui::Button::new("Reset all")).clicked() {
+                            reset = Some(None);
+                        }
+                    });
+                });
+            if let Some(name) = reset {
+                match executor::persistent::reset(&state_file, name.as_deref()) {
+                    Ok(()) => self.logs.push(format!(
+                        "[System] Reset stored value of {}",
+                        name.as_deref().unwrap_or("all persistent variables")
+                    )),
+                    Err(e) => self.logs.push(format!("[System] [Error] {:#}", e)),
+                }
+                self.stored_state = None;
+            }
+            if changed {
+                self.undo_stack.push(&self.graph);
+            }
+            self.show_variables = show_variables;
+        }
+
+        // Execution Settings Window - per-graph step budget and watchdog policy
+        if self.show_exec_settings {
+            let mut show_exec_settings = self.show_exec_settings;
+            let mut changed = false;
+            egui::Window::new("⏱ Execution Settings")
+                .open(&mut show_exec_settings)
+                .resizable(false)
+                .default_width(260.0)
+                .show(ctx, |ui| {
+                    let settings = &mut self.graph.settings;
+                    // Commit DragValue edits once, not on every drag frame
+                    let committed = |r: &egui::Response| r.drag_stopped() || (r.changed() && !r.dragged());
+
+                    let mut limit_steps = settings.max_steps.is_some();
+                    ui.horizontal(|ui| {
+                        if ui.checkbox(&mut limit_steps, "Max steps").on_hover_text("Nodes executed by the whole run, across all flows and loop bodies").changed() {
+                            settings.max_steps = limit_steps.then_some(100_000);
+                            changed = true;
+                        }
+                        if let Some(max) = settings.max_steps.as_mut() {
+                            let r = ui.add(egui::DragValue::new(max).range(1..=u64::MAX).speed(100));
+                            changed |= committed(&r);
+                        } else {
+                            ui.weak("Unlimited");
+                        }
+                    });
+
+                    let mut limit_time = settings.max_duration_secs.is_some();
+                    ui.horizontal(|ui| {
+                        if ui.checkbox(&mut limit_time, "Max run time").on_hover_text("Wall-clock limit for the whole run").changed() {
+                            settings.max_duration_secs = limit_time.then_some(3600);
+                            changed = true;
+                        }
+                        if let Some(secs) = settings.max_duration_secs.as_mut() {
+                            let r = ui.add(egui::DragValue::new(secs).range(1..=u64::MAX).suffix(" s"));
+                            changed |= committed(&r);
+                        } else {
+                            ui.weak("Unlimited");
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("On limit:");
+                        egui::ComboBox::from_id_salt("on_limit")
+                            .selected_text(settings.on_limit.label())
+                            .show_ui(ui, |ui| {
+                                for action in graph::LimitAction::ALL {
+                                    changed |= ui
+                                        .selectable_value(&mut settings.on_limit, action, action.label())
+                                        .changed();
+                                }
+                            });
+                    });
+
+                    ui.separator();
+                    if ui.button("Reset to defaults").clicked() {
+                        *settings = graph::ExecutionSettings::default();
+                        changed = true;
+                    }
+                    ui.weak("Saved with the graph. Applies to the next run.");
+                });
+            if changed {
+                self.undo_stack.push(&self.graph);
+            }
+            self.show_exec_settings = show_exec_settings;
+        }
+
+        // Functions Window - the script's function library
+        if self.show_functions {
+            let mut show_functions = self.show_functions;
+            let mut changed = false;
+            egui::Window::new("ƒ Functions")
+                .open(&mut show_functions)
+                .default_width(320.0)
+                .show(ctx, |ui| {
+                    let mut rename = None;
+                    let mut remove = None;
+                    for function in &self.graph.functions {
+                        ui.horizontal(|ui| {
+                            let editing = self.editing_function.as_deref() == Some(function.name.as_str());
+                            let mut name = function.name.clone();
+                            let r = ui.add(egui::TextEdit::singleline(&mut name).desired_width(160.0));
+                            if r.changed() {
+                                rename = Some((function.name.clone(), name));
+                            }
+                            let (params, results) = function.signature();
+                            ui.weak(format!("({}) → ({})", params.join(", "), results.join(", ")));
+                            if ui.selectable_label(editing, "✏").on_hover_text("Edit body").clicked() {
+                                self.editing_function = Some(function.name.clone());
+                            }
+                            if ui.small_button("🗑").clicked() {
+                                remove = Some(function.name.clone());
+                            }
+                        });
+                    }
+                    if let Some((old, new)) = rename
+                        && !new.is_empty()
+                        && self.graph.function(&new).is_none()
+                    {
+                        self.graph.rename_function(&old, &new);
+                        if self.editing_function.as_deref() == Some(old.as_str()) {
+                            self.editing_function = Some(new);
+                        }
+                        changed = true;
+                    }
+                    if let Some(name) = remove {
+                        self.graph.functions.retain(|f| f.name != name);
+                        if self.editing_function.as_deref() == Some(name.as_str()) {
+                            self.editing_function = None;
+                        }
+                        changed = true;
+                    }
+                    if ui.button("➕ New function").clicked() {
+                        let name = editor::functions::free_function_name(&self.graph, "Function");
+                        self.graph.functions.push(editor::functions::new_function(&name));
+                        self.editing_function = Some(name);
+                        changed = true;
+                    }
+                    ui.separator();
+                    ui.weak("Input Param / Output Param nodes in a body become the ports of its Call Function nodes.");
+                    ui.weak("Right-click a selection → Collapse to Function to extract one.");
+                });
+            if changed {
+                self.graph.sync_function_calls();
+                self.undo_stack.push(&self.graph);
+            }
+            self.show_functions = show_functions;
+        }
+
+        // Schedules Window - schedules saved with the script, plus the scheduler state
+        if self.show_schedules {
+            let mut show_schedules = self.show_schedules;
+            let mut changed = false;
+            let mut start_scheduler = false;
+            let mut stop_scheduler = false;
+            egui::Window::new("⏰ Schedules")
+                .open(&mut show_schedules)
+                .default_width(420.0)
+                .show(ctx, |ui| {
+                    let now = chrono::Local::now();
+                    let mut remove = None;
+                    for (i, schedule) in self.graph.schedules.iter_mut().enumerate() {
+                        let parsed = scheduler::spec::ScheduleSpec::parse(&schedule.spec);
+                        ui.horizontal(|ui| {
+                            changed |= ui.checkbox(&mut schedule.enabled, "").changed();
+                            let text_color = if parsed.is_ok() {
+                                ui.style().visuals.text_color()
+                            } else {
+                                egui::Color32::RED
+                            };
+                            let r = ui.add(
+                                egui::TextEdit::singleline(&mut schedule.spec)
+                                    .desired_width(240.0)
+                                    .text_color(text_color),
+                            );
+                            changed |= r.lost_focus();
+                            match &parsed {
+                                Ok(spec) => {
+                                    let next = spec
+                                        .next_after(now)
+                                        .map_or("never".to_string(), scheduler::spec::format_time);
+                                    ui.weak(format!("next {}", next));
+                                }
+                                Err(e) => {
+                                    ui.colored_label(egui::Color32::RED, "⚠").on_hover_text(format!("{:#}", e));
+                                }
+                            }
+                            if ui.small_button("🗑").clicked() {
+                                remove = Some(i);
+                            }
+                        });
+                    }
+                    if let Some(i) = remove {
+                        self.graph.schedules.remove(i);
+                        changed = true;
+                    }
+                    if ui.button("➕ Add schedule").clicked() {
+                        self.graph.schedules.push(graph::Schedule {
+                            spec: "0 9 * * 1-5".to_string(),
+                            enabled: true,
+                        });
+                        changed = true;
+                    }
+                    ui.collapsing("Formats", |ui| {
+                        ui.label("Cron: minute hour day month weekday");
+                        ui.weak("30 8 * * 1-5  → 08:30 on weekdays");
+                        ui.weak("*/10 * * * *  → every 10 minutes");
+                        ui.label("Interval:");
+                        ui.weak("every 15 minutes between 09:00 and 17:30");
+                    });
+
+                    ui.separator();
+                    match &self.scheduler {
+                        Some(handle) => {
+                            ui.horizontal(|ui| {
+                                ui.colored_label(egui::Color32::from_rgb(50, 180, 50), "● Scheduler running");
+                                if ui.button("⏹ Stop scheduler").clicked() {
+                                    stop_scheduler = true;
+                                }
+                            });
+                            egui::Grid::new("schedule_status").striped(true).show(ui, |ui| {
+                                ui.strong("Schedule");
+                                ui.strong("Next");
+                                ui.strong("Last run");
+                                ui.strong("Result");
+                                ui.end_row();
+                                for job in handle.status() {
+                                    ui.label(&job.spec);
+                                    ui.label(job.next_fire.map_or("never".to_string(), scheduler::spec::format_time));
+                                    ui.label(job.last_fire.map_or("-".to_string(), scheduler::spec::format_time));
+                                    ui.label(job.last_result.map_or("-".to_string(), |r| r.label()));
+                                    ui.end_row();
+                                }
+                            });
+                            ui.weak("Schedule edits apply when the scheduler is restarted.");
+                        }
+                        None => {
+                            if ui.button("▶ Start scheduler").clicked() {
+                                start_scheduler = true;
+                            }
+                            ui.weak("Keeps running this script at the times above while the app is open.");
+                        }
+                    }
+                });
+            if changed {
+                self.undo_stack.push(&self.graph);
+            }
+            if stop_scheduler {
+                // Dropping the handle stops the scheduler; its channel closes shortly after
+                self.scheduler = None;
+            }
+            if start_scheduler {
+                let (tx, rx) = std::sync::mpsc::channel();
+                let script = scheduler::ScheduledScript {
+                    name: self.script_name.clone(),
+                    graph: self.graph.clone(),
+                    state_file: Some(self.state_file()),
+                };
+                match scheduler::start(vec![script], tx) {
+                    Ok(handle) => {
+                        self.scheduler = Some(handle);
+                        self.scheduler_rx = Some(rx);
+                    }
+                    Err(e) => self.logs.push(format!("[Scheduler] [Error] {:#}", e)),
+                }
+            }
+            self.show_schedules = show_schedules;
+        }
+
         // Style Settings Window
         let mut show_style_window = self.show_style_window;
         if show_style_window {
@@ -1053,8 +1732,10 @@ impl eframe::App for MyApp {
         self.show_load_window = show_load_window;
 
         if let Some((json, name)) = loaded_script {
-            if let Ok(graph) = serde_json::from_str(&json) {
+            if let Ok(graph) = BlueprintGraph::from_json(&json) {
                 self.graph = graph;
+                self.editing_function = None;
+                self.stored_state = None;
                 self.script_name = name.clone();
                 // Load History
                 let history_path = format!("scripts/{}.history", name);
@@ -1236,6 +1917,10 @@ impl eframe::App for MyApp {
                                 NodeType::BlueprintFunction { name } => name.clone(),
                                 NodeType::GetVariable { name } => format!("Get: {}", name),
                                 NodeType::SetVariable { name } => format!("Set: {}", name),
+                                NodeType::InputParam { name } => format!("Param: {}", name),
+                                NodeType::OutputParam { name } => format!("Result: {}", name),
+                                NodeType::CallFunction { name } => format!("Call: {}", name),
+                                NodeType::CallScript { name } => format!("Script: {}", name),
                                 NodeType::Add => "Add".into(),
                                 NodeType::Subtract => "Subtract".into(),
                                 NodeType::Multiply => "Multiply".into(),
@@ -1248,6 +1933,15 @@ impl eframe::App for MyApp {
                                 NodeType::Clamp => "Clamp".into(),
                                 NodeType::Random => "Random".into(),
                                 NodeType::Constant => "Constant".into(),
+                                NodeType::MakePoint => "Make Point".into(),
+                                NodeType::SplitPoint => "Split Point".into(),
+                                NodeType::MakeRect => "Make Rect".into(),
+                                NodeType::SplitRect => "Split Rect".into(),
+                                NodeType::RectCenter => "Rect Center".into(),
+                                NodeType::RectOffset => "Rect Offset".into(),
+                                NodeType::PointInRect => "Point In Rect".into(),
+                                NodeType::RectUnion => "Rect Union".into(),
+                                NodeType::Distance => "Distance".into(),
                                 NodeType::ToInteger => "To Integer".into(),
                                 NodeType::ToFloat => "To Float".into(),
                                 NodeType::ToString => "To String".into(),
@@ -1258,10 +1952,29 @@ impl eframe::App for MyApp {
                                 NodeType::Delay => "Delay".into(),
                                 NodeType::GetTimestamp => "Get Timestamp".into(),
                                 NodeType::Sequence => "Sequence".into(),
+                                NodeType::Parallel => "Parallel".into(),
+                                NodeType::Join => "Join".into(),
                                 NodeType::Gate => "Gate".into(),
                                 NodeType::WaitForCondition => "Wait For Condition".into(),
                                 NodeType::ForLoopAsync => "For Loop Async".into(),
+                                NodeType::ForEach => "For Each".into(),
                                 NodeType::ForEachLine => "For Each Line".into(),
+                                NodeType::TryCatch => "Try Catch".into(),
+                                NodeType::Break => "Break".into(),
+                                NodeType::Continue => "Continue".into(),
+                                NodeType::Return => "Return".into(),
+                                NodeType::SwitchOnString => "Switch On String".into(),
+                                NodeType::SwitchOnInt => "Switch On Int".into(),
+                                NodeType::DoOnce => "Do Once".into(),
+                                NodeType::DoN => "Do N".into(),
+                                NodeType::FlipFlop => "Flip Flop".into(),
+                                NodeType::Retry => "Retry".into(),
+                                NodeType::WithTimeout => "With Timeout".into(),
+                                NodeType::OnStart => "On Start".into(),
+                                NodeType::OnInterval => "On Interval".into(),
+                                NodeType::OnVariableChanged => "On Variable Changed".into(),
+                                NodeType::OnHotkey => "On Hotkey".into(),
+                                NodeType::OnStop => "On Stop".into(),
                                 NodeType::Equals => "Equals".into(),
                                 NodeType::NotEquals => "Not Equals".into(),
                                 NodeType::GreaterThan => "Greater Than".into(),
@@ -1280,6 +1993,10 @@ impl eframe::App for MyApp {
                                 NodeType::Format => "Format".into(),
                                 NodeType::StringJoin => "String Join".into(),
                                 NodeType::StringBetween => "String Between".into(),
+                                NodeType::RegexMatch => "Regex Match".into(),
+                                NodeType::RegexCapture => "Regex Capture".into(),
+                                NodeType::RegexFindAll => "Regex Find All".into(),
+                                NodeType::RegexReplace => "Regex Replace".into(),
                                 NodeType::StringTrim => "String Trim".into(),
                                 NodeType::ReadInput => "Read Input".into(),
                                 NodeType::FileRead => "File Read".into(),
@@ -1322,9 +2039,23 @@ impl eframe::App for MyApp {
                                         .copied()
                                         .unwrap_or(egui::Color32::from_rgb(180, 50, 50))
                                 }
+                                NodeType::OnStart
+                                | NodeType::OnInterval
+                                | NodeType::OnVariableChanged
+                                | NodeType::OnHotkey
+                                | NodeType::OnStop => {
+                                    self.editor
+                                        .style
+                                        .header_colors
+                                        .get("Event")
+                                        .copied()
+                                        .unwrap_or(egui::Color32::from_rgb(180, 50, 50))
+                                }
                                 NodeType::BlueprintFunction { .. }
-                                | NodeType::InputParam
-                                | NodeType::OutputParam => self
+                                | NodeType::InputParam { .. }
+                                | NodeType::OutputParam { .. }
+                                | NodeType::CallFunction { .. }
+                                | NodeType::CallScript { .. } => self
                                     .editor
                                     .style
                                     .header_colors
@@ -1343,7 +2074,16 @@ impl eframe::App for MyApp {
                                 | NodeType::Max
                                 | NodeType::Clamp
                                 | NodeType::Random
-                                | NodeType::Constant => self
+                                | NodeType::Constant
+                                | NodeType::MakePoint
+                                | NodeType::SplitPoint
+                                | NodeType::MakeRect
+                                | NodeType::SplitRect
+                                | NodeType::RectCenter
+                                | NodeType::RectOffset
+                                | NodeType::PointInRect
+                                | NodeType::RectUnion
+                                | NodeType::Distance => self
                                     .editor
                                     .style
                                     .header_colors
@@ -1368,7 +2108,11 @@ impl eframe::App for MyApp {
                                 | NodeType::StringJoin
                                 | NodeType::StringBetween
                                 | NodeType::ExtractAfter
-                                | NodeType::ExtractUntil => self
+                                | NodeType::ExtractUntil
+                                | NodeType::RegexMatch
+                                | NodeType::RegexCapture
+                                | NodeType::RegexFindAll
+                                | NodeType::RegexReplace => self
                                     .editor
                                     .style
                                     .header_colors
@@ -1407,8 +2151,21 @@ impl eframe::App for MyApp {
                                 // Control Flow
                                 NodeType::Branch
                                 | NodeType::ForLoop
+                                | NodeType::ForEach
                                 | NodeType::WhileLoop
                                 | NodeType::Sequence
+                                | NodeType::Parallel
+                                | NodeType::Join
+                                | NodeType::Break
+                                | NodeType::Continue
+                                | NodeType::Return
+                                | NodeType::SwitchOnString
+                                | NodeType::SwitchOnInt
+                                | NodeType::DoOnce
+                                | NodeType::DoN
+                                | NodeType::FlipFlop
+                                | NodeType::Retry
+                                | NodeType::WithTimeout
                                 | NodeType::Gate
                                 | NodeType::Entry => self
                                     .editor
@@ -1474,6 +2231,22 @@ impl eframe::App for MyApp {
                                 | NodeType::ArrayGet
                                 | NodeType::ArraySet
                                 | NodeType::ArrayLength
+                                | NodeType::ArraySort
+                                | NodeType::ArraySlice
+                                | NodeType::ArrayJoin
+                                | NodeType::ArrayIndexOf
+                                | NodeType::ArrayContains
+                                | NodeType::ArrayReverse
+                                | NodeType::ArrayUnique
+                                | NodeType::ArrayRemoveAt
+                                | NodeType::ArrayFilter
+                                | NodeType::ArrayMap
+                                | NodeType::MapCreate
+                                | NodeType::MapGet
+                                | NodeType::MapSet
+                                | NodeType::MapRemove
+                                | NodeType::MapKeys
+                                | NodeType::MapHasKey
                                 | NodeType::JSONParse
                                 | NodeType::JSONStringify
                                 | NodeType::HTTPRequest => self
@@ -1722,7 +2495,36 @@ impl eframe::App for MyApp {
                 }
             }
 
-            self.editor.show(ui, &mut self.graph, &mut self.undo_stack);
+            // Keep CallFunction / CallScript ports in line with their signatures
+            self.graph.sync_function_calls();
+            self.script_signatures.sync(&mut self.graph);
+
+            let body_index = self
+                .editing_function
+                .as_ref()
+                .and_then(|name| self.graph.functions.iter().position(|f| &f.name == name));
+            match body_index {
+                Some(index) => {
+                    // Body edits are recorded as edits of the whole script
+                    let mut body = std::mem::take(&mut self.graph.functions[index].body);
+                    let mut body_edits = UndoStack::default();
+                    self.editor.show(ui, &mut body, &mut body_edits);
+                    self.graph.functions[index].body = body;
+                    if !body_edits.history.is_empty() {
+                        self.undo_stack.push(&self.graph);
+                    }
+                    if self.editor.collapse_requested {
+                        self.collapse_selection(Some(index));
+                    }
+                }
+                None => {
+                    self.editing_function = None;
+                    self.editor.show(ui, &mut self.graph, &mut self.undo_stack);
+                    if self.editor.collapse_requested {
+                        self.collapse_selection(None);
+                    }
+                }
+            }
         });
 
         // Context menu
@@ -1732,3 +2534,33 @@ impl eframe::App for MyApp {
         }
     }
 }
+
+/// Initial value typed in the Variables window: a number, `true` / `false`,
+/// or text
+fn parse_initial_value(text: &str) -> graph::VariableValue {
+    if let Ok(i) = text.parse::<i64>() {
+        graph::VariableValue::Integer(i)
+    } else if let Ok(f) = text.parse::<f64>() {
+        graph::VariableValue::Float(f)
+    } else if let Ok(b) = text.parse::<bool>() {
+        graph::VariableValue::Boolean(b)
+    } else {
+        graph::VariableValue::String(text.to_string())
+    }
+}
+
+fn data_type_of(value: &graph::VariableValue) -> DataType {
+    match value {
+        graph::VariableValue::Boolean(_) => DataType::Boolean,
+        graph::VariableValue::Integer(_) => DataType::Integer,
+        graph::VariableValue::Float(_) => DataType::Float,
+        graph::VariableValue::String(_) => DataType::String,
+        graph::VariableValue::Vector3(..) => DataType::Vector3,
+        graph::VariableValue::Point(..) => DataType::Point,
+        graph::VariableValue::Rect(..) => DataType::Rect,
+        graph::VariableValue::Array(_) => DataType::Array,
+        graph::VariableValue::Map(_) => DataType::Map,
+        graph::VariableValue::Image(_) => DataType::Image,
+        graph::VariableValue::None => DataType::Custom("Any".into()),
+    }
+}
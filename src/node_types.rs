@@ -8,8 +8,16 @@ pub enum DataType {
     Float,
     String,
     Vector3,
+    /// Screen position
+    Point,
+    /// Screen area
+    Rect,
     /// Array type for Module H: Data Operations
     Array,
+    /// String-keyed map, e.g. a parsed JSON object
+    Map,
+    /// In-memory image, e.g. a screen capture
+    Image,
     Custom(String),
 }
 
@@ -43,6 +51,25 @@ pub enum NodeType {
     Random,
     /// Constant value output - outputs the input value directly
     Constant,
+    // Geometry
+    /// Make a point from X and Y
+    MakePoint,
+    /// Split a point into X and Y
+    SplitPoint,
+    /// Make a rect from its top-left corner and size
+    MakeRect,
+    /// Split a rect into corner and size
+    SplitRect,
+    /// Center point of a rect
+    RectCenter,
+    /// Move a rect by DX, DY
+    RectOffset,
+    /// Check whether a point lies inside a rect
+    PointInRect,
+    /// Smallest rect containing two rects
+    RectUnion,
+    /// Distance between two points
+    Distance,
     // Comparison operations
     Equals,
     NotEquals,
@@ -71,14 +98,36 @@ pub enum NodeType {
     StringBetween,
     /// Trim whitespace from string with mode options
     StringTrim,
+    /// Check whether a regular expression matches a string
+    RegexMatch,
+    /// Capture groups of the first regular expression match
+    RegexCapture,
+    /// Every match of a regular expression
+    RegexFindAll,
+    /// Replace every match of a regular expression
+    RegexReplace,
     // I/O
     ReadInput,
     FileRead,
     FileWrite,
-    // Other
-    InputParam,
-    OutputParam,
-    // Entry point for the graph
+    // User functions
+    /// Argument `name` of the function call, inside a function body
+    InputParam {
+        name: String,
+    },
+    /// Result `name` of the function call, inside a function body
+    OutputParam {
+        name: String,
+    },
+    /// Call a user function of the graph (`BlueprintGraph::functions`)
+    CallFunction {
+        name: String,
+    },
+    /// Run the saved script `scripts/<name>.json` to completion
+    CallScript {
+        name: String,
+    },
+    /// Where a function body starts running
     Entry,
     // Type conversions
     ToInteger,
@@ -108,6 +157,38 @@ pub enum NodeType {
     ArraySet,
     /// Get the length of an array
     ArrayLength,
+    /// Sort an array, ascending or descending
+    ArraySort,
+    /// Part of an array between two indices
+    ArraySlice,
+    /// Join the elements of an array into a string
+    ArrayJoin,
+    /// Index of the first element equal to a value
+    ArrayIndexOf,
+    /// Check whether an array has an element equal to a value
+    ArrayContains,
+    /// Remove the element at an index from an array variable
+    ArrayRemoveAt,
+    /// Reverse the order of an array
+    ArrayReverse,
+    /// Remove repeated elements from an array
+    ArrayUnique,
+    /// Keep the elements for which a per-element subgraph says Keep
+    ArrayFilter,
+    /// Transform each element through a per-element subgraph
+    ArrayMap,
+    /// Create an empty map
+    MapCreate,
+    /// Get the value of a key from a map
+    MapGet,
+    /// Set the value of a key in a map variable
+    MapSet,
+    /// Remove a key from a map variable
+    MapRemove,
+    /// Get the keys of a map as an array
+    MapKeys,
+    /// Check whether a map has a key
+    MapHasKey,
     /// Parse a JSON string into a value
     JSONParse,
     /// Convert a value to a JSON string
@@ -170,8 +251,96 @@ pub enum NodeType {
     WaitForCondition,
     /// For loop that waits for Continue signal before each iteration
     ForLoopAsync,
+    /// Iterate over each element of an array
+    ForEach,
     /// Iterate over each line in a multi-line text string
     ForEachLine,
+    /// Run a body and route any node failure inside it to a Catch branch
+    TryCatch,
+    /// Run each output branch on its own thread at the same time
+    Parallel,
+    /// Wait for all (or the first of) the branches of a Parallel node
+    Join,
+    /// Exit the innermost loop
+    Break,
+    /// Skip to the next iteration of the innermost loop
+    Continue,
+    /// End the current event flow or function call
+    Return,
+    /// Continue on the exec output of the case equal to `Value`, else `Default`
+    SwitchOnString,
+    /// Same as SwitchOnString, comparing integers
+    SwitchOnInt,
+    /// Pass the flow through once, until Reset
+    DoOnce,
+    /// Pass the flow through N times, until Reset
+    DoN,
+    /// Alternate between the A and B outputs
+    FlipFlop,
+    /// Re-run a body with backoff until it succeeds or runs out of attempts
+    Retry,
+    /// Run a body, cancelling it if it runs longer than the timeout
+    WithTimeout,
+    // Events
+    /// Runs once when execution starts
+    OnStart,
+    /// Runs every `Interval (ms)` until execution is stopped
+    OnInterval,
+    /// Runs each time the variable named by its `Variable` input is written
+    OnVariableChanged,
+    /// Runs each time its key combination is pressed, even while unfocused
+    OnHotkey,
+    /// Cleanup: runs once after every other flow has ended, including after Stop
+    OnStop,
+}
+
+impl NodeType {
+    /// Event entry points; a run starts from these (see `executor::triggers`)
+    pub fn is_event(&self) -> bool {
+        match self {
+            NodeType::BlueprintFunction { name } => name == "Event Tick",
+            NodeType::OnStart
+            | NodeType::OnInterval
+            | NodeType::OnVariableChanged
+            | NodeType::OnHotkey
+            | NodeType::OnStop => true,
+            _ => false,
+        }
+    }
+
+    /// Nodes with a name field under their header (variable, parameter or
+    /// function name)
+    pub fn has_name_field(&self) -> bool {
+        matches!(
+            self,
+            NodeType::GetVariable { .. }
+                | NodeType::SetVariable { .. }
+                | NodeType::InputParam { .. }
+                | NodeType::OutputParam { .. }
+                | NodeType::CallFunction { .. }
+                | NodeType::CallScript { .. }
+        )
+    }
+
+    /// Nodes that can fail at runtime. They get an optional `Error` exec
+    /// output and an `ErrorMessage` data output.
+    pub fn has_error_flow(&self) -> bool {
+        matches!(
+            self,
+            NodeType::RunCommand
+                | NodeType::LaunchApp
+                | NodeType::CloseApp
+                | NodeType::FileWrite
+                | NodeType::HTTPRequest
+                | NodeType::ScreenCapture
+                | NodeType::SaveScreenshot
+                | NodeType::RegionCapture
+                | NodeType::FindImage
+                | NodeType::WaitForImage
+                | NodeType::CallFunction { .. }
+                | NodeType::CallScript { .. }
+        )
+    }
 }
 
 impl Default for NodeType {
@@ -0,0 +1,284 @@
+//! # Scheduler
+//!
+//! Launches [`Interpreter`] runs of scripts at the times declared in their
+//! [`Schedule`]s. Used by the GUI schedule panel and by the
+//! `--schedule <script>...` headless mode.
+//!
+//! ## Schedule formats
+//! - Cron, 5 fields: `minute hour day-of-month month day-of-week`, e.g.
+//!   `30 8 * * 1-5` (08:30 on weekdays) or `*/10 * * * *`
+//! - `every N minutes`, optionally `between HH:MM and HH:MM`, e.g.
+//!   `every 15 minutes between 09:00 and 17:30`
+//!
+//! Times are local. A schedule that comes due while a run of its script is
+//! still going, started by it or by another schedule of the same script, is
+//! skipped, and the skip is recorded as its last result. Runs of one script
+//! therefore never overlap or write its state file at the same time.
+
+pub mod spec;
+
+use crate::executor::events::ExecutionEvent;
+use crate::executor::{ExecutionHandle, Interpreter, RunOptions};
+use crate::graph::{BlueprintGraph, Schedule};
+use anyhow::{Result, bail};
+use chrono::{DateTime, Local};
+use spec::{ScheduleSpec, format_time};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender, TryRecvError, channel};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How often the scheduler thread checks for due schedules and finished runs
+const TICK: Duration = Duration::from_millis(250);
+
+/// A script handed to the scheduler, with the schedules stored in its graph.
+pub struct ScheduledScript {
+    pub name: String,
+    pub graph: BlueprintGraph,
+    /// Where the script keeps its persistent variables, if anywhere
+    pub state_file: Option<PathBuf>,
+}
+
+/// Outcome of the latest launch of a schedule
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunResult {
+    Running,
+    Succeeded,
+    /// First error reported by the run
+    Failed(String),
+    Stopped,
+    /// Came due while a run of the same script was still going
+    Skipped,
+}
+
+impl RunResult {
+    pub fn label(&self) -> String {
+        match self {
+            RunResult::Running => "Running".to_string(),
+            RunResult::Succeeded => "Succeeded".to_string(),
+            RunResult::Failed(e) => format!("Failed: {}", e),
+            RunResult::Stopped => "Stopped".to_string(),
+            RunResult::Skipped => "Skipped (still running)".to_string(),
+        }
+    }
+}
+
+/// Snapshot of one schedule, for display.
+#[derive(Debug, Clone)]
+pub struct JobStatus {
+    pub script: String,
+    pub spec: String,
+    pub next_fire: Option<DateTime<Local>>,
+    pub last_fire: Option<DateTime<Local>>,
+    pub last_result: Option<RunResult>,
+}
+
+struct ActiveRun {
+    handle: ExecutionHandle,
+    rx: Receiver<ExecutionEvent>,
+    error: Option<String>,
+}
+
+struct Job {
+    /// Index of the script in the list handed to [`start`]
+    script: usize,
+    status: JobStatus,
+    schedule: ScheduleSpec,
+    graph: Arc<BlueprintGraph>,
+    state_file: Option<PathBuf>,
+    run: Option<ActiveRun>,
+}
+
+impl Job {
+    /// Forward the run's logs and record its result once it has finished
+    fn poll(&mut self, tx: &Sender<ExecutionEvent>) {
+        let Some(run) = self.run.as_mut() else {
+            return;
+        };
+        let script = &self.status.script;
+        loop {
+            match run.rx.try_recv() {
+                Ok(ExecutionEvent::Log(msg)) => {
+                    let _ = tx.send(ExecutionEvent::Log(format!("[{}] {}", script, msg)));
+                }
+                Ok(ExecutionEvent::Error(msg)) => {
+                    let _ = tx.send(ExecutionEvent::Error(format!("[{}] {}", script, msg)));
+                    run.error.get_or_insert(msg);
+                }
+                Ok(_) => {}
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+
+        let result = if run.handle.was_stopped() {
+            RunResult::Stopped
+        } else if let Some(error) = run.error.take() {
+            RunResult::Failed(error)
+        } else {
+            RunResult::Succeeded
+        };
+        let _ = tx.send(ExecutionEvent::Log(format!(
+            "Scheduler: '{}' finished - {}",
+            script,
+            result.label()
+        )));
+        self.status.last_result = Some(result);
+        self.run = None;
+    }
+
+    /// Launch a run if the schedule is due, then move to the next fire time.
+    /// `script_busy` is set while any schedule of the script has a run going.
+    fn fire_if_due(&mut self, now: DateTime<Local>, script_busy: bool, tx: &Sender<ExecutionEvent>) {
+        if self.status.next_fire.is_none_or(|next| next > now) {
+            return;
+        }
+        self.status.last_fire = Some(now);
+        self.status.next_fire = self.schedule.next_after(now);
+
+        if script_busy {
+            let _ = tx.send(ExecutionEvent::Log(format!(
+                "Scheduler: '{}' skipped ({}), a run of the script is still going",
+                self.status.script, self.status.spec
+            )));
+            self.status.last_result = Some(RunResult::Skipped);
+            return;
+        }
+
+        let _ = tx.send(ExecutionEvent::Log(format!(
+            "Scheduler: Starting '{}' ({})",
+            self.status.script, self.status.spec
+        )));
+        let (run_tx, rx) = channel();
+        let options = RunOptions {
+            state_file: self.state_file.clone(),
+            script: Some(self.status.script.clone()),
+            ..Default::default()
+        };
+        self.run = Some(ActiveRun {
+            handle: Interpreter::spawn_with_options(&self.graph, run_tx, options),
+            rx,
+            error: None,
+        });
+        self.status.last_result = Some(RunResult::Running);
+    }
+}
+
+/// Handle to a running scheduler, returned by [`start`]. Dropping it stops
+/// the scheduler and any scheduled run in progress.
+pub struct SchedulerHandle {
+    status: Arc<Mutex<Vec<JobStatus>>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl SchedulerHandle {
+    /// Current state of every schedule
+    pub fn status(&self) -> Vec<JobStatus> {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Stop launching runs and stop the runs in progress
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Block until the scheduler thread exits (after [`stop`](Self::stop))
+    pub fn join(mut self) {
+        if let Some(t) = self.thread.take() {
+            let _ = t.join();
+        }
+    }
+}
+
+impl Drop for SchedulerHandle {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Start scheduling the enabled schedules of `scripts`.
+///
+/// Run logs are forwarded to `tx` prefixed with the script name, along with
+/// the scheduler's own messages. Fails if a schedule cannot be parsed or no
+/// schedule is enabled.
+pub fn start(scripts: Vec<ScheduledScript>, tx: Sender<ExecutionEvent>) -> Result<SchedulerHandle> {
+    let now = Local::now();
+    let mut jobs = Vec::new();
+    for (index, script) in scripts.into_iter().enumerate() {
+        let graph = Arc::new(script.graph);
+        for Schedule { spec, enabled } in &graph.schedules {
+            if !enabled {
+                continue;
+            }
+            let schedule = ScheduleSpec::parse(spec)
+                .map_err(|e| anyhow::anyhow!("Invalid schedule '{}' in {}: {}", spec, script.name, e))?;
+            jobs.push(Job {
+                script: index,
+                status: JobStatus {
+                    script: script.name.clone(),
+                    spec: spec.clone(),
+                    next_fire: schedule.next_after(now),
+                    last_fire: None,
+                    last_result: None,
+                },
+                schedule,
+                graph: graph.clone(),
+                state_file: script.state_file.clone(),
+                run: None,
+            });
+        }
+    }
+    if jobs.is_empty() {
+        bail!("No enabled schedules");
+    }
+
+    for job in &jobs {
+        let next = job.status.next_fire.map_or("never".to_string(), format_time);
+        let _ = tx.send(ExecutionEvent::Log(format!(
+            "Scheduler: '{}' ({}) next at {}",
+            job.status.script, job.status.spec, next
+        )));
+    }
+
+    let status = Arc::new(Mutex::new(jobs.iter().map(|j| j.status.clone()).collect()));
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread = {
+        let (status, stop) = (status.clone(), stop.clone());
+        thread::spawn(move || run_scheduler(jobs, status, stop, tx))
+    };
+
+    Ok(SchedulerHandle {
+        status,
+        stop,
+        thread: Some(thread),
+    })
+}
+
+fn run_scheduler(
+    mut jobs: Vec<Job>,
+    status: Arc<Mutex<Vec<JobStatus>>>,
+    stop: Arc<AtomicBool>,
+    tx: Sender<ExecutionEvent>,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        let now = Local::now();
+        for job in &mut jobs {
+            job.poll(&tx);
+        }
+        for i in 0..jobs.len() {
+            let script = jobs[i].script;
+            let busy = jobs.iter().any(|j| j.script == script && j.run.is_some());
+            jobs[i].fire_if_due(now, busy, &tx);
+        }
+        *status.lock().unwrap() = jobs.iter().map(|j| j.status.clone()).collect();
+        thread::sleep(TICK);
+    }
+
+    for run in jobs.iter().filter_map(|j| j.run.as_ref()) {
+        run.handle.stop();
+    }
+    let _ = tx.send(ExecutionEvent::Log("Scheduler: Stopped".to_string()));
+}
@@ -0,0 +1,390 @@
+//! Schedule expressions and next fire time computation.
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+/// How far ahead to search for a matching cron time (e.g. `0 0 29 2 *`)
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const DAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A parsed [`Schedule::spec`](crate::graph::Schedule::spec).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleSpec {
+    Cron(Cron),
+    /// Every `minutes` from `from` until `to` (inclusive) each day. A window
+    /// with `to` before `from` runs overnight.
+    Every {
+        minutes: u32,
+        from: NaiveTime,
+        to: NaiveTime,
+    },
+}
+
+impl ScheduleSpec {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        if spec.to_lowercase().starts_with("every") {
+            parse_every(spec)
+        } else {
+            Cron::parse(spec).map(ScheduleSpec::Cron)
+        }
+    }
+
+    /// First fire time strictly after `after`, on a whole minute
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            ScheduleSpec::Cron(cron) => cron.next_after(after),
+            ScheduleSpec::Every { minutes, from, to } => {
+                let step = Duration::minutes(*minutes as i64);
+                let mut window = *to - *from;
+                if window < Duration::zero() {
+                    window += Duration::days(1);
+                }
+
+                // Start one day back to catch an overnight window still open
+                let first_day = after.date_naive() - Duration::days(1);
+                (0..3).find_map(|offset| {
+                    let start = (first_day + Duration::days(offset)).and_time(*from);
+                    let mut time = start;
+                    while time - start <= window {
+                        if let Some(local) = to_local(time).filter(|t| *t > after) {
+                            return Some(local);
+                        }
+                        time += step;
+                    }
+                    None
+                })
+            }
+        }
+    }
+}
+
+/// Standard 5-field cron expression: `minute hour day-of-month month day-of-week`.
+///
+/// Fields accept `*`, numbers, lists (`1,15`), ranges (`9-17`) and steps
+/// (`*/10`, `0-30/5`); months and weekdays also accept names (`jan`, `mon`).
+/// Sunday is `0` or `7`. As in cron, when both day fields are restricted a
+/// time matches if either one does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Cron {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            bail!(
+                "expected 5 fields (minute hour day month weekday), found {}",
+                fields.len()
+            );
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7, &DAY_NAMES).context("weekday")?;
+        // 7 is Sunday too
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[]).context("minute")?,
+            hours: parse_field(hour, 0, 23, &[]).context("hour")?,
+            days: parse_field(day, 1, 31, &[]).context("day of month")?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES).context("month")?,
+            weekdays,
+            // `*/2` is still unrestricted for the day-of-month / weekday rule
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+
+    fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let start = after.date_naive();
+        for offset in 0..MAX_LOOKAHEAD_DAYS {
+            let date = start + Duration::days(offset);
+            if !self.matches_date(date) {
+                continue;
+            }
+            for hour in (0..24).filter(|h| self.hours & (1 << h) != 0) {
+                for minute in (0..60).filter(|m| self.minutes & (1 << m) != 0) {
+                    let Some(time) = date.and_hms_opt(hour, minute, 0) else {
+                        continue;
+                    };
+                    if let Some(local) = to_local(time).filter(|t| *t > after) {
+                        return Some(local);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Parse one cron field into a bit set of allowed values
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64> {
+    let value = |s: &str| -> Result<u32> {
+        let lower = s.to_lowercase();
+        if let Some(index) = names.iter().position(|n| *n == lower) {
+            // Month names start at 1, day names at 0 (Sunday)
+            return Ok(index as u32 + min);
+        }
+        let v: u32 = s.parse().map_err(|_| anyhow!("'{}' is not a number", s))?;
+        if v < min || v > max {
+            bail!("{} is outside {}-{}", v, min, max);
+        }
+        Ok(v)
+    };
+
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| anyhow!("invalid step '{}'", step))?;
+                if step == 0 {
+                    bail!("step must be at least 1");
+                }
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((a, b)) => (value(a)?, value(b)?),
+                // `5/15` means from 5 to the end
+                None if step.is_some() => (value(range)?, max),
+                None => {
+                    let v = value(range)?;
+                    (v, v)
+                }
+            },
+        };
+        if start > end {
+            bail!("range {}-{} is reversed", start, end);
+        }
+
+        for v in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            bits |= 1 << v;
+        }
+    }
+    Ok(bits)
+}
+
+/// `every N minutes [between HH:MM and HH:MM]` (`hours` also accepted)
+fn parse_every(spec: &str) -> Result<ScheduleSpec> {
+    let lower = spec.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+
+    let (count, unit, window) = match words[..] {
+        ["every", count, unit] => (count, unit, None),
+        ["every", count, unit, "between", from, "and", to] => (count, unit, Some((from, to))),
+        _ => bail!("expected 'every N minutes' or 'every N minutes between HH:MM and HH:MM'"),
+    };
+
+    let count: u32 = count
+        .parse()
+        .map_err(|_| anyhow!("'{}' is not a number", count))?;
+    let minutes = match unit {
+        "minute" | "minutes" | "min" | "mins" => count,
+        "hour" | "hours" => count
+            .checked_mul(60)
+            .ok_or_else(|| anyhow!("{} hours is too long an interval", count))?,
+        _ => bail!("unknown unit '{}', use minutes or hours", unit),
+    };
+    if minutes == 0 {
+        bail!("interval must be at least 1 minute");
+    }
+
+    let time = |s: &str| {
+        NaiveTime::parse_from_str(s, "%H:%M").map_err(|_| anyhow!("'{}' is not a HH:MM time", s))
+    };
+    let (from, to) = match window {
+        Some((from, to)) => (time(from)?, time(to)?),
+        None => (NaiveTime::MIN, NaiveTime::from_hms_opt(23, 59, 0).unwrap()),
+    };
+
+    Ok(ScheduleSpec::Every { minutes, from, to })
+}
+
+/// Local time for a wall-clock time; `None` inside a DST gap
+fn to_local(time: NaiveDateTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&time).earliest()
+}
+
+/// Format a fire time for logs and the schedule panel
+pub fn format_time(time: DateTime<Local>) -> String {
+    time.format("%Y-%m-%d %H:%M").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn local(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(y, m, d, h, min, s).unwrap()
+    }
+
+    fn bits(values: &[u32]) -> u64 {
+        values.iter().fold(0, |bits, v| bits | 1 << v)
+    }
+
+    fn cron(expr: &str) -> Cron {
+        Cron::parse(expr).unwrap()
+    }
+
+    #[test]
+    fn field_ranges_steps_and_lists() {
+        assert_eq!(parse_field("*", 0, 5, &[]).unwrap(), bits(&[0, 1, 2, 3, 4, 5]));
+        assert_eq!(parse_field("*/15", 0, 59, &[]).unwrap(), bits(&[0, 15, 30, 45]));
+        assert_eq!(parse_field("9-12", 0, 23, &[]).unwrap(), bits(&[9, 10, 11, 12]));
+        assert_eq!(parse_field("0-30/10", 0, 59, &[]).unwrap(), bits(&[0, 10, 20, 30]));
+        assert_eq!(parse_field("50/5", 0, 59, &[]).unwrap(), bits(&[50, 55]));
+        assert_eq!(parse_field("1,15,31", 1, 31, &[]).unwrap(), bits(&[1, 15, 31]));
+    }
+
+    #[test]
+    fn field_names() {
+        assert_eq!(parse_field("mon-fri", 0, 7, &DAY_NAMES).unwrap(), bits(&[1, 2, 3, 4, 5]));
+        assert_eq!(parse_field("SUN,sat", 0, 7, &DAY_NAMES).unwrap(), bits(&[0, 6]));
+        assert_eq!(parse_field("jan,mar-may", 1, 12, &MONTH_NAMES).unwrap(), bits(&[1, 3, 4, 5]));
+        assert_eq!(parse_field("dec", 1, 12, &MONTH_NAMES).unwrap(), bits(&[12]));
+    }
+
+    #[test]
+    fn invalid_fields() {
+        assert!(parse_field("60", 0, 59, &[]).is_err());
+        assert!(parse_field("0", 1, 31, &[]).is_err());
+        assert!(parse_field("10-5", 0, 59, &[]).is_err());
+        assert!(parse_field("*/0", 0, 59, &[]).is_err());
+        assert!(parse_field("abc", 0, 59, &[]).is_err());
+        assert!(Cron::parse("0 0 * *").is_err());
+    }
+
+    #[test]
+    fn sunday_as_seven() {
+        let sunday = date(2026, 1, 18);
+        assert!(cron("0 0 * * 7").matches_date(sunday));
+        assert!(cron("0 0 * * 0").matches_date(sunday));
+        assert!(cron("0 0 * * 5-7").matches_date(sunday));
+        assert!(!cron("0 0 * * 7").matches_date(date(2026, 1, 19)));
+    }
+
+    #[test]
+    fn day_of_month_or_weekday() {
+        // Both restricted: either one matches
+        let friday_13 = cron("0 0 13 * fri");
+        assert!(friday_13.matches_date(date(2026, 1, 2))); // Friday
+        assert!(friday_13.matches_date(date(2026, 1, 13))); // Tuesday the 13th
+        assert!(!friday_13.matches_date(date(2026, 1, 14)));
+
+        // One unrestricted: both must match
+        let mondays = cron("0 0 * * mon");
+        assert!(mondays.matches_date(date(2026, 1, 5)));
+        assert!(!mondays.matches_date(date(2026, 1, 6)));
+
+        // A step over `*` counts as unrestricted
+        let odd_mondays = cron("0 0 */2 * mon");
+        assert!(odd_mondays.matches_date(date(2026, 1, 5)));
+        assert!(!odd_mondays.matches_date(date(2026, 1, 12)));
+        assert!(!odd_mondays.matches_date(date(2026, 1, 3)));
+    }
+
+    #[test]
+    fn cron_next_after_midnight() {
+        let daily = ScheduleSpec::parse("0 0 * * *").unwrap();
+        assert_eq!(
+            daily.next_after(local(2026, 1, 15, 23, 59, 30)),
+            Some(local(2026, 1, 16, 0, 0, 0))
+        );
+        // Strictly after: a fire time equal to `after` is skipped
+        assert_eq!(
+            daily.next_after(local(2026, 1, 16, 0, 0, 0)),
+            Some(local(2026, 1, 17, 0, 0, 0))
+        );
+
+        let late = ScheduleSpec::parse("30 23 * * *").unwrap();
+        assert_eq!(
+            late.next_after(local(2026, 1, 31, 23, 30, 0)),
+            Some(local(2026, 2, 1, 23, 30, 0))
+        );
+        assert_eq!(
+            late.next_after(local(2026, 1, 31, 0, 5, 0)),
+            Some(local(2026, 1, 31, 23, 30, 0))
+        );
+    }
+
+    #[test]
+    fn every_overnight_window() {
+        let spec = ScheduleSpec::parse("every 30 minutes between 22:00 and 02:00").unwrap();
+        assert_eq!(
+            spec.next_after(local(2026, 1, 15, 23, 50, 0)),
+            Some(local(2026, 1, 16, 0, 0, 0))
+        );
+        assert_eq!(
+            spec.next_after(local(2026, 1, 16, 1, 45, 0)),
+            Some(local(2026, 1, 16, 2, 0, 0))
+        );
+        assert_eq!(
+            spec.next_after(local(2026, 1, 16, 2, 0, 0)),
+            Some(local(2026, 1, 16, 22, 0, 0))
+        );
+        assert_eq!(
+            spec.next_after(local(2026, 1, 16, 12, 0, 0)),
+            Some(local(2026, 1, 16, 22, 0, 0))
+        );
+    }
+
+    #[test]
+    fn every_without_window() {
+        let spec = ScheduleSpec::parse("Every 2 hours").unwrap();
+        assert_eq!(
+            spec,
+            ScheduleSpec::Every {
+                minutes: 120,
+                from: NaiveTime::MIN,
+                to: NaiveTime::from_hms_opt(23, 59, 0).unwrap(),
+            }
+        );
+        assert_eq!(
+            spec.next_after(local(2026, 1, 15, 23, 0, 0)),
+            Some(local(2026, 1, 16, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn invalid_every() {
+        assert!(ScheduleSpec::parse("every 0 minutes").is_err());
+        assert!(ScheduleSpec::parse("every 5 days").is_err());
+        assert!(ScheduleSpec::parse("every 99999999 hours").is_err());
+        assert!(ScheduleSpec::parse("every 10 minutes between 9 and 17").is_err());
+    }
+}